@@ -0,0 +1,349 @@
+//! Firebase App Check module.
+//!
+//! This module verifies App Check tokens sent by client apps, so that backends can reject
+//! requests that do not come from genuine instances of their apps, and mints App Check tokens
+//! for apps that use a custom attestation provider.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! # use firebase_admin_sdk::FirebaseApp;
+//! # async fn run(app: FirebaseApp, token_from_header: &str) {
+//! let app_check = app.app_check();
+//!
+//! match app_check.verify_token(token_from_header).await {
+//!     Ok(verified) => println!("Request from app {}", verified.app_id),
+//!     Err(e) => eprintln!("Rejecting request: {}", e),
+//! }
+//! # }
+//! ```
+
+use crate::auth::keys::{JwksKeySource, KeyFetchError, KeySource};
+use crate::core::clock::{Clock, SystemClock};
+use crate::core::http::{build_client, build_public_client};
+use crate::core::middleware::AuthMiddleware;
+use crate::core::signer::{sign_jwt, SignerError};
+use crate::core::telemetry::Operation;
+use crate::core::FirebaseError;
+use jsonwebtoken::{decode, decode_header, Algorithm, Validation};
+use reqwest::header;
+use reqwest_middleware::ClientWithMiddleware;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::{Duration, UNIX_EPOCH};
+use thiserror::Error;
+
+const APP_CHECK_API: &str = "https://firebaseappcheck.googleapis.com/v1";
+/// The JWKS used to sign App Check tokens.
+pub const APP_CHECK_JWKS_URL: &str = "https://firebaseappcheck.googleapis.com/v1/jwks";
+/// The issuer prefix of App Check tokens; the project number follows it.
+const APP_CHECK_ISSUER: &str = "https://firebaseappcheck.googleapis.com/";
+/// The audience of custom tokens exchanged for App Check tokens.
+const CUSTOM_TOKEN_AUDIENCE: &str =
+    "https://firebaseappcheck.googleapis.com/google.firebase.appcheck.v1.TokenExchangeService";
+/// The lifetime of the custom tokens exchanged for App Check tokens.
+const CUSTOM_TOKEN_LIFETIME: Duration = Duration::from_secs(300);
+const MIN_TOKEN_TTL: Duration = Duration::from_secs(30 * 60);
+const MAX_TOKEN_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+/// The clock skew tolerated when checking `exp` and `iat`.
+const DEFAULT_LEEWAY: Duration = Duration::from_secs(60);
+
+/// Errors that can occur during App Check operations.
+#[derive(Error, Debug)]
+pub enum AppCheckError {
+    /// Wrapper for `reqwest::Error`.
+    #[error("HTTP Request failed: {0}")]
+    RequestError(#[from] reqwest::Error),
+    /// Wrapper for `reqwest_middleware::Error`.
+    #[error("Middleware error: {0}")]
+    MiddlewareError(#[from] reqwest_middleware::Error),
+    /// Wrapper for `serde_json::Error`.
+    #[error("Serialization error: {0}")]
+    SerializationError(#[from] serde_json::Error),
+    /// Errors returned by the App Check API.
+    #[error("API error: {0}")]
+    ApiError(FirebaseError),
+    /// The signing keys could not be fetched, or the token's key is unknown.
+    #[error("Key fetch error: {0}")]
+    KeyFetchError(#[from] KeyFetchError),
+    /// The token signature or format is invalid.
+    #[error("JWT error: {0}")]
+    JwtError(#[from] jsonwebtoken::errors::Error),
+    /// The token is not a valid App Check token for this project.
+    #[error("Invalid token: {0}")]
+    InvalidToken(String),
+    /// The token has expired.
+    #[error("Token expired")]
+    Expired,
+    /// An argument was rejected before sending the request (e.g., an out of range TTL).
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
+    /// The custom token exchanged for an App Check token could not be signed.
+    #[error("Signing error: {0}")]
+    SigningError(#[from] SignerError),
+}
+
+impl From<FirebaseError> for AppCheckError {
+    fn from(error: FirebaseError) -> Self {
+        AppCheckError::ApiError(error)
+    }
+}
+
+/// The claims of a verified App Check token.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecodedAppCheckToken {
+    /// The issuer, `https://firebaseappcheck.googleapis.com/{project_number}`.
+    pub iss: String,
+    /// The App ID the token was issued for.
+    pub sub: String,
+    /// The audiences (`projects/{project_number}` and `projects/{project_id}`).
+    pub aud: Vec<String>,
+    /// Expiration time, in seconds since the Unix epoch.
+    pub exp: usize,
+    /// Issue time, in seconds since the Unix epoch.
+    pub iat: usize,
+    /// All other claims.
+    #[serde(flatten)]
+    pub claims: serde_json::Map<String, serde_json::Value>,
+}
+
+/// The result of a successful `verify_token` call.
+#[derive(Debug, Clone)]
+pub struct VerifyAppCheckTokenResponse {
+    /// The App ID the token was issued for.
+    pub app_id: String,
+    /// The decoded token.
+    pub token: DecodedAppCheckToken,
+}
+
+/// An App Check token minted by `create_token`.
+#[derive(Debug, Clone)]
+pub struct AppCheckToken {
+    /// The App Check token.
+    pub token: String,
+    /// How long the token is valid for.
+    pub ttl: Duration,
+}
+
+/// Claims of the custom token exchanged for an App Check token.
+#[derive(Debug, Serialize)]
+struct CustomTokenClaims {
+    iss: String,
+    sub: String,
+    aud: &'static str,
+    iat: u64,
+    exp: u64,
+    app_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ttl: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExchangeCustomTokenRequest {
+    custom_token: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExchangeCustomTokenResponse {
+    token: String,
+    ttl: Option<String>,
+}
+
+/// Client for interacting with Firebase App Check.
+#[derive(Clone)]
+pub struct FirebaseAppCheck {
+    client: ClientWithMiddleware,
+    base_url: String,
+    middleware: AuthMiddleware,
+    key_source: Arc<dyn KeySource>,
+    clock: Arc<dyn Clock>,
+}
+
+impl FirebaseAppCheck {
+    /// Creates a new `FirebaseAppCheck` instance.
+    ///
+    /// This is typically called via `FirebaseApp::app_check()`.
+    pub fn new(middleware: AuthMiddleware) -> Self {
+        Self::new_with_url(middleware, APP_CHECK_API.to_string())
+    }
+
+    /// Creates a new `FirebaseAppCheck` instance that sends token exchange requests to
+    /// `base_url` (e.g., `http://localhost:8080/v1`).
+    pub fn new_with_url(middleware: AuthMiddleware, base_url: String) -> Self {
+        let client = build_client(&middleware, "app_check");
+        let key_source = middleware.key_source(APP_CHECK_JWKS_URL, || {
+            let client = build_public_client(&middleware, "app_check");
+            Arc::new(JwksKeySource::new(APP_CHECK_JWKS_URL).with_client(client))
+        });
+
+        Self {
+            client,
+            base_url,
+            middleware,
+            key_source,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Sets the source of the keys used to verify tokens (e.g., a `StaticKeySource` in tests).
+    pub fn with_key_source(mut self, key_source: impl KeySource + 'static) -> Self {
+        self.key_source = Arc::new(key_source);
+        self
+    }
+
+    /// Sets the clock used to check `exp` and `iat`.
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Verifies an App Check token.
+    ///
+    /// Checks the signature against the App Check JWKS, the issuer, that the audience includes
+    /// this project, and the expiration time.
+    pub async fn verify_token(&self, token: &str) -> Result<VerifyAppCheckTokenResponse, AppCheckError> {
+        let header = decode_header(token)?;
+        if header.alg != Algorithm::RS256 {
+            return Err(AppCheckError::InvalidToken(format!(
+                "Unexpected algorithm {:?}, expected RS256",
+                header.alg
+            )));
+        }
+        if header.typ.as_deref() != Some("JWT") {
+            return Err(AppCheckError::InvalidToken("Unexpected token type, expected JWT".to_string()));
+        }
+        let kid = header
+            .kid
+            .ok_or_else(|| AppCheckError::InvalidToken("Missing kid in header".to_string()))?;
+        let key = self.key_source.get_key(&kid).await?;
+
+        let mut validation = Validation::new(Algorithm::RS256);
+        validation.validate_aud = false;
+        validation.validate_exp = false;
+        validation.validate_nbf = false;
+        let claims = decode::<DecodedAppCheckToken>(token, &key, &validation)?.claims;
+
+        let audience = format!("projects/{}", self.middleware.project_id());
+        if !claims.aud.contains(&audience) {
+            return Err(AppCheckError::InvalidToken(format!(
+                "Audience {:?} does not include {}",
+                claims.aud, audience
+            )));
+        }
+        if !claims.iss.starts_with(APP_CHECK_ISSUER) {
+            return Err(AppCheckError::InvalidToken(format!("Unexpected issuer {}", claims.iss)));
+        }
+        if claims.sub.is_empty() {
+            return Err(AppCheckError::InvalidToken("Subject (sub) claim must not be empty".to_string()));
+        }
+
+        let now = self.now_secs() as usize;
+        let leeway = DEFAULT_LEEWAY.as_secs() as usize;
+        if now > claims.exp.saturating_add(leeway) {
+            return Err(AppCheckError::Expired);
+        }
+        if claims.iat > now.saturating_add(leeway) {
+            return Err(AppCheckError::InvalidToken("Token issued in the future".to_string()));
+        }
+
+        Ok(VerifyAppCheckTokenResponse {
+            app_id: claims.sub.clone(),
+            token: claims,
+        })
+    }
+
+    /// Creates an App Check token for the given app, e.g. for a custom attestation provider.
+    ///
+    /// The exchanged custom token is signed by the app's signer (see
+    /// [`AuthMiddleware::signer`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `app_id` - The App ID of the Firebase app.
+    /// * `ttl` - How long the token is valid for, between 30 minutes and 7 days. Defaults to
+    ///   one hour.
+    pub async fn create_token(&self, app_id: &str, ttl: Option<Duration>) -> Result<AppCheckToken, AppCheckError> {
+        if app_id.is_empty() {
+            return Err(AppCheckError::InvalidArgument("App ID must not be empty".to_string()));
+        }
+        if let Some(ttl) = ttl {
+            if !(MIN_TOKEN_TTL..=MAX_TOKEN_TTL).contains(&ttl) {
+                return Err(AppCheckError::InvalidArgument(
+                    "TTL must be between 30 minutes and 7 days".to_string(),
+                ));
+            }
+        }
+
+        let signer = self.middleware.signer()?;
+        let client_email = signer.account_email().await?;
+        let now = self.now_secs();
+        let claims = CustomTokenClaims {
+            iss: client_email.clone(),
+            sub: client_email,
+            aud: CUSTOM_TOKEN_AUDIENCE,
+            iat: now,
+            exp: now + CUSTOM_TOKEN_LIFETIME.as_secs(),
+            app_id: app_id.to_string(),
+            ttl: ttl.map(format_duration),
+        };
+        let custom_token = sign_jwt(signer.as_ref(), &claims).await?;
+
+        let url = format!(
+            "{}/projects/{}/apps/{}:exchangeCustomToken",
+            self.base_url,
+            self.middleware.project_id(),
+            app_id
+        );
+        let response = self
+            .client
+            .post(&url)
+            .with_extension(Operation("create_token"))
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(serde_json::to_vec(&ExchangeCustomTokenRequest { custom_token })?)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(FirebaseError::from_response(response, "Create App Check token failed").await.into());
+        }
+
+        let result: ExchangeCustomTokenResponse = response.json().await?;
+        let ttl = result
+            .ttl
+            .as_deref()
+            .and_then(parse_duration)
+            .ok_or_else(|| AppCheckError::InvalidToken("Missing or invalid ttl in response".to_string()))?;
+
+        Ok(AppCheckToken {
+            token: result.token,
+            ttl,
+        })
+    }
+
+    fn now_secs(&self) -> u64 {
+        self.clock
+            .now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+    }
+}
+
+/// Formats a duration in the protobuf JSON format (e.g., `3600s`, `1.5s`).
+fn format_duration(duration: Duration) -> String {
+    if duration.subsec_nanos() == 0 {
+        format!("{}s", duration.as_secs())
+    } else {
+        format!("{}s", duration.as_secs_f64())
+    }
+}
+
+/// Parses a duration in the protobuf JSON format (e.g., `3600s`, `3600.5s`).
+fn parse_duration(value: &str) -> Option<Duration> {
+    let seconds: f64 = value.strip_suffix('s')?.parse().ok()?;
+    Duration::try_from_secs_f64(seconds).ok()
+}
+
+#[cfg(test)]
+mod tests;
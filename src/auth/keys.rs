@@ -1,48 +1,113 @@
+//! Public key sources for verifying Firebase tokens.
+//!
+//! Tokens are verified with keys looked up by the `kid` header through a [`KeySource`]. The
+//! SDK fetches Google's x509 certificates by default; JWKS endpoints and static PEM maps
+//! (e.g., for air-gapped test environments) are also supported.
+
+use async_trait::async_trait;
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::DecodingKey;
+use reqwest::Client;
+use reqwest_middleware::ClientWithMiddleware;
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::RwLock;
-use reqwest::Client;
 use std::time::{Duration, Instant};
 use thiserror::Error;
+use tokio::sync::RwLock;
 
-const GOOGLE_PUBLIC_KEYS_URL: &str = "https://www.googleapis.com/robot/v1/metadata/x509/securetoken@system.gserviceaccount.com";
+/// The x509 certificates used to sign Firebase ID tokens.
+pub const ID_TOKEN_CERTS_URL: &str =
+    "https://www.googleapis.com/robot/v1/metadata/x509/securetoken@system.gserviceaccount.com";
+/// The x509 certificates used to sign Firebase session cookies.
+pub const SESSION_COOKIE_CERTS_URL: &str =
+    "https://www.googleapis.com/identitytoolkit/v3/relyingparty/publicKeys";
+
+/// How long fetched keys are cached when the response has no `max-age` directive.
+const DEFAULT_CACHE_DURATION: Duration = Duration::from_secs(3600);
+/// The minimum time between fetches caused by tokens with an unknown key ID.
+const MIN_REFETCH_INTERVAL: Duration = Duration::from_secs(60);
 
 #[derive(Error, Debug)]
 pub enum KeyFetchError {
     #[error("Network error: {0}")]
     NetworkError(#[from] reqwest::Error),
+    /// Wrapper for `reqwest_middleware::Error`.
+    #[error("Middleware error: {0}")]
+    MiddlewareError(#[from] reqwest_middleware::Error),
     #[error("Failed to parse keys")]
     ParseError,
+    /// No key with the given ID is known.
+    #[error("Unknown key ID: {0}")]
+    UnknownKey(String),
+    /// A key could not be turned into a verification key.
+    #[error("Invalid key: {0}")]
+    InvalidKey(#[from] jsonwebtoken::errors::Error),
+}
+
+/// Provides the public keys used to verify token signatures.
+#[async_trait]
+pub trait KeySource: Send + Sync {
+    /// Returns the key with the given key ID (the `kid` token header).
+    async fn get_key(&self, kid: &str) -> Result<DecodingKey, KeyFetchError>;
+}
+
+#[async_trait]
+impl<T: KeySource + ?Sized> KeySource for Arc<T> {
+    async fn get_key(&self, kid: &str) -> Result<DecodingKey, KeyFetchError> {
+        (**self).get_key(kid).await
+    }
 }
 
 #[derive(Clone)]
 struct CachedKeys {
-    keys: HashMap<String, String>,
+    keys: HashMap<String, DecodingKey>,
+    fetched_at: Instant,
     expires_at: Instant,
 }
 
-pub struct PublicKeyManager {
-    client: Client,
-    cache: Arc<RwLock<Option<CachedKeys>>>,
+/// The format of the keys served by an HTTP key endpoint.
+#[derive(Debug, Clone, Copy)]
+enum KeyFormat {
+    /// A JSON object mapping key IDs to PEM encoded x509 certificates.
+    X509,
+    /// A JSON Web Key Set.
+    Jwks,
 }
 
-impl PublicKeyManager {
-    pub fn new() -> Self {
+/// Fetches keys over HTTP and caches them as long as the response's `Cache-Control` allows.
+///
+/// An unknown key ID triggers a refetch (keys may have rotated), at most once per
+/// [`MIN_REFETCH_INTERVAL`].
+struct HttpKeyCache {
+    client: ClientWithMiddleware,
+    url: String,
+    format: KeyFormat,
+    cache: RwLock<Option<CachedKeys>>,
+}
+
+impl HttpKeyCache {
+    fn new(url: String, format: KeyFormat) -> Self {
         Self {
-            client: Client::new(),
-            cache: Arc::new(RwLock::new(None)),
+            client: ClientWithMiddleware::from(Client::new()),
+            url,
+            format,
+            cache: RwLock::new(None),
         }
     }
 
-    pub async fn get_key(&self, kid: &str) -> Result<String, KeyFetchError> {
+    async fn get_key(&self, kid: &str) -> Result<DecodingKey, KeyFetchError> {
         // Check cache first
         {
             let cache = self.cache.read().await;
             if let Some(cached) = &*cache {
-                if Instant::now() < cached.expires_at {
+                let now = Instant::now();
+                if now < cached.expires_at {
                     if let Some(key) = cached.keys.get(kid) {
                         return Ok(key.clone());
                     }
+                    if now < cached.fetched_at + MIN_REFETCH_INTERVAL {
+                        return Err(KeyFetchError::UnknownKey(kid.to_string()));
+                    }
                 }
             }
         }
@@ -52,41 +117,170 @@ impl PublicKeyManager {
 
         // Check cache again
         let cache = self.cache.read().await;
-        if let Some(cached) = &*cache {
-            cached.keys.get(kid).cloned().ok_or(KeyFetchError::ParseError)
-        } else {
-            Err(KeyFetchError::ParseError)
-        }
+        cache
+            .as_ref()
+            .and_then(|cached| cached.keys.get(kid).cloned())
+            .ok_or_else(|| KeyFetchError::UnknownKey(kid.to_string()))
     }
 
     async fn refresh_keys(&self) -> Result<(), KeyFetchError> {
-        let response = self.client.get(GOOGLE_PUBLIC_KEYS_URL).send().await?;
+        let response = self.client.get(&self.url).send().await?.error_for_status()?;
 
         // Parse Cache-Control header
-        let max_age = response.headers()
+        let max_age = response
+            .headers()
             .get(reqwest::header::CACHE_CONTROL)
             .and_then(|h| h.to_str().ok())
             .and_then(|s| {
-                s.split(',')
-                    .find_map(|part| {
-                        let part = part.trim();
-                        if part.starts_with("max-age=") {
-                            part.trim_start_matches("max-age=").parse::<u64>().ok()
-                        } else {
-                            None
-                        }
-                    })
+                s.split(',').find_map(|part| {
+                    part.trim()
+                        .strip_prefix("max-age=")
+                        .and_then(|age| age.parse::<u64>().ok())
+                })
             })
-            .unwrap_or(3600); // Default to 1 hour if missing
+            .map_or(DEFAULT_CACHE_DURATION, Duration::from_secs);
 
-        let keys_json: HashMap<String, String> = response.json().await?;
+        let keys = match self.format {
+            KeyFormat::X509 => {
+                let certs: HashMap<String, String> =
+                    response.json().await.map_err(|_| KeyFetchError::ParseError)?;
+                parse_pem_keys(&certs)?
+            }
+            KeyFormat::Jwks => {
+                let set: JwkSet = response.json().await.map_err(|_| KeyFetchError::ParseError)?;
+                parse_jwks(&set)?
+            }
+        };
 
+        let now = Instant::now();
         let mut cache = self.cache.write().await;
         *cache = Some(CachedKeys {
-            keys: keys_json,
-            expires_at: Instant::now() + Duration::from_secs(max_age),
+            keys,
+            fetched_at: now,
+            expires_at: now + max_age,
         });
 
         Ok(())
     }
 }
+
+fn parse_pem_keys(pems: &HashMap<String, String>) -> Result<HashMap<String, DecodingKey>, KeyFetchError> {
+    pems.iter()
+        .map(|(kid, pem)| Ok((kid.clone(), DecodingKey::from_rsa_pem(pem.as_bytes())?)))
+        .collect()
+}
+
+fn parse_jwks(set: &JwkSet) -> Result<HashMap<String, DecodingKey>, KeyFetchError> {
+    set.keys
+        .iter()
+        .filter_map(|jwk| {
+            let kid = jwk.common.key_id.clone()?;
+            Some(DecodingKey::from_jwk(jwk).map(|key| (kid, key)).map_err(Into::into))
+        })
+        .collect()
+}
+
+/// Fetches x509 certificates from an HTTP endpoint (Google's format: a JSON object mapping key
+/// IDs to PEM certificates).
+pub struct PublicKeyManager {
+    inner: HttpKeyCache,
+}
+
+impl Default for PublicKeyManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PublicKeyManager {
+    /// Creates a key source for the certificates used to sign Firebase ID tokens.
+    pub fn new() -> Self {
+        Self::with_url(ID_TOKEN_CERTS_URL)
+    }
+
+    /// Creates a key source for the certificates served at `url`.
+    pub fn with_url(url: impl Into<String>) -> Self {
+        Self {
+            inner: HttpKeyCache::new(url.into(), KeyFormat::X509),
+        }
+    }
+
+    /// Fetches the certificates through `client` (e.g., one honoring the app's proxy and
+    /// transport) instead of a default `reqwest` client.
+    pub fn with_client(mut self, client: ClientWithMiddleware) -> Self {
+        self.inner.client = client;
+        self
+    }
+
+    pub async fn get_key(&self, kid: &str) -> Result<DecodingKey, KeyFetchError> {
+        self.inner.get_key(kid).await
+    }
+}
+
+#[async_trait]
+impl KeySource for PublicKeyManager {
+    async fn get_key(&self, kid: &str) -> Result<DecodingKey, KeyFetchError> {
+        self.inner.get_key(kid).await
+    }
+}
+
+/// Fetches a JSON Web Key Set from an HTTP endpoint.
+pub struct JwksKeySource {
+    inner: HttpKeyCache,
+}
+
+impl JwksKeySource {
+    /// Creates a key source for the JWKS served at `url`.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            inner: HttpKeyCache::new(url.into(), KeyFormat::Jwks),
+        }
+    }
+
+    /// Fetches the key set through `client` (e.g., one honoring the app's proxy and
+    /// transport) instead of a default `reqwest` client.
+    pub fn with_client(mut self, client: ClientWithMiddleware) -> Self {
+        self.inner.client = client;
+        self
+    }
+}
+
+#[async_trait]
+impl KeySource for JwksKeySource {
+    async fn get_key(&self, kid: &str) -> Result<DecodingKey, KeyFetchError> {
+        self.inner.get_key(kid).await
+    }
+}
+
+/// A fixed set of keys that never touches the network.
+#[derive(Clone)]
+pub struct StaticKeySource {
+    keys: HashMap<String, DecodingKey>,
+}
+
+impl StaticKeySource {
+    /// Creates a key source from a map of key IDs to PEM encoded RSA public keys or x509
+    /// certificates.
+    pub fn from_pem_map(pems: &HashMap<String, String>) -> Result<Self, KeyFetchError> {
+        Ok(Self {
+            keys: parse_pem_keys(pems)?,
+        })
+    }
+
+    /// Creates a key source from a JSON Web Key Set.
+    pub fn from_jwks(set: &JwkSet) -> Result<Self, KeyFetchError> {
+        Ok(Self {
+            keys: parse_jwks(set)?,
+        })
+    }
+}
+
+#[async_trait]
+impl KeySource for StaticKeySource {
+    async fn get_key(&self, kid: &str) -> Result<DecodingKey, KeyFetchError> {
+        self.keys
+            .get(kid)
+            .cloned()
+            .ok_or_else(|| KeyFetchError::UnknownKey(kid.to_string()))
+    }
+}
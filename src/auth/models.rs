@@ -1,5 +1,7 @@
 //! Data models for Firebase Authentication.
 
+use crate::auth::AuthError;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
 /// Represents a user in the Firebase project.
@@ -32,10 +34,30 @@ pub struct UserRecord {
     pub custom_attributes: Option<String>,
     /// The user's tenant ID (for multi-tenancy).
     pub tenant_id: Option<String>,
+    /// Tokens issued before this time (seconds since the Unix epoch) are revoked.
+    pub valid_since: Option<String>,
     /// Multi-factor authentication info.
     pub mfa_info: Option<Vec<MfaInfo>>,
 }
 
+impl UserRecord {
+    /// Returns the time, in seconds since the Unix epoch, before which the user's tokens are
+    /// considered revoked.
+    pub fn tokens_valid_after_time(&self) -> Option<u64> {
+        self.valid_since.as_deref()?.parse().ok()
+    }
+
+    /// Deserializes the user's custom claims into `T`.
+    ///
+    /// Returns `Ok(None)` if the user has no custom claims.
+    pub fn custom_claims<T: DeserializeOwned>(&self) -> Result<Option<T>, serde_json::Error> {
+        self.custom_attributes
+            .as_deref()
+            .map(serde_json::from_str)
+            .transpose()
+    }
+}
+
 /// Metadata associated with a user account.
 #[derive(Debug, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -78,10 +100,159 @@ pub struct MfaInfo {
     pub display_name: Option<String>,
     /// The phone number info for this MFA method.
     pub phone_info: Option<String>,
+    /// Set for TOTP (authenticator app) second factors.
+    pub totp_info: Option<TotpInfo>,
     /// The date and time this MFA method was enrolled.
     pub enrolled_at: Option<String>,
 }
 
+impl MfaInfo {
+    /// Returns the type of this second factor.
+    pub fn factor_id(&self) -> MultiFactorId {
+        if self.phone_info.is_some() {
+            MultiFactorId::Phone
+        } else if self.totp_info.is_some() {
+            MultiFactorId::Totp
+        } else {
+            MultiFactorId::Unknown
+        }
+    }
+}
+
+/// The type of a second factor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MultiFactorId {
+    /// SMS to a phone number.
+    Phone,
+    /// Time-based one-time password from an authenticator app.
+    Totp,
+    /// A factor type not known to this SDK version.
+    Unknown,
+}
+
+/// TOTP second factor details. The API exposes no fields for it.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct TotpInfo {}
+
+/// A second factor to enroll when creating or updating a user.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiFactorEnrollment {
+    /// The enrollment ID of an existing second factor to keep. Generated for new phone
+    /// factors if unset.
+    #[serde(rename = "mfaEnrollmentId", skip_serializing_if = "Option::is_none")]
+    pub uid: Option<String>,
+    /// The display name of the second factor.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    /// The enrollment time (RFC 3339), for factors migrated from another system.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enrolled_at: Option<String>,
+    /// The second factor.
+    #[serde(flatten)]
+    pub factor: SecondFactor,
+}
+
+/// The type-specific details of a [`MultiFactorEnrollment`].
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub enum SecondFactor {
+    /// SMS to the given phone number (E.164).
+    #[serde(rename = "phoneInfo")]
+    Phone(String),
+    /// A TOTP authenticator. TOTP factors are enrolled by the user; updates can only keep an
+    /// existing one, identified by its `uid`.
+    #[serde(rename = "totpInfo")]
+    Totp(TotpInfo),
+}
+
+impl MultiFactorEnrollment {
+    /// A phone second factor.
+    pub fn phone(phone_number: impl Into<String>) -> Self {
+        Self {
+            uid: None,
+            display_name: None,
+            enrolled_at: None,
+            factor: SecondFactor::Phone(phone_number.into()),
+        }
+    }
+
+    /// An existing TOTP second factor, identified by its enrollment ID.
+    pub fn totp(uid: impl Into<String>) -> Self {
+        Self {
+            uid: Some(uid.into()),
+            display_name: None,
+            enrolled_at: None,
+            factor: SecondFactor::Totp(TotpInfo::default()),
+        }
+    }
+
+    /// Sets the enrollment ID.
+    pub fn with_uid(mut self, uid: impl Into<String>) -> Self {
+        self.uid = Some(uid.into());
+        self
+    }
+
+    /// Sets the display name.
+    pub fn with_display_name(mut self, display_name: impl Into<String>) -> Self {
+        self.display_name = Some(display_name.into());
+        self
+    }
+
+    /// Sets the enrollment time (RFC 3339).
+    pub fn with_enrolled_at(mut self, enrolled_at: impl Into<String>) -> Self {
+        self.enrolled_at = Some(enrolled_at.into());
+        self
+    }
+
+    /// Checks that the factor can be sent to the API.
+    pub(crate) fn validate(&self) -> Result<(), AuthError> {
+        match &self.factor {
+            SecondFactor::Phone(phone_number) if phone_number.is_empty() => Err(
+                AuthError::InvalidArgument("Phone second factors require a phone number".to_string()),
+            ),
+            SecondFactor::Totp(_) if self.uid.is_none() => Err(AuthError::InvalidArgument(
+                "TOTP second factors can only be kept by enrollment ID".to_string(),
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A federated identity to link to a user with `FirebaseAuth::update_user`.
+#[derive(Debug, Clone, Serialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct UserProvider {
+    /// The ID of the identity provider (e.g., `google.com`).
+    pub provider_id: String,
+    /// The user's ID at the identity provider.
+    #[serde(rename = "rawId")]
+    pub uid: String,
+    /// The user's email at the identity provider.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    /// The user's display name at the identity provider.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    /// The user's photo URL at the identity provider.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub photo_url: Option<String>,
+}
+
+/// Serializes second factors as the `mfa` field of `accounts:update`; an empty list removes
+/// all second factors.
+fn serialize_mfa_update<S: serde::Serializer>(
+    enrollments: &Option<Vec<MultiFactorEnrollment>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    use serde::ser::SerializeMap;
+
+    let mut map = serializer.serialize_map(None)?;
+    if let Some(enrollments) = enrollments.as_ref().filter(|e| !e.is_empty()) {
+        map.serialize_entry("enrollments", enrollments)?;
+    }
+    map.end()
+}
+
 /// Request to create a new user.
 #[derive(Debug, Serialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -110,6 +281,9 @@ pub struct CreateUserRequest {
     /// The user's phone number.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub phone_number: Option<String>,
+    /// Phone second factors to enroll.
+    #[serde(rename = "mfaInfo", skip_serializing_if = "Option::is_none")]
+    pub multi_factor: Option<Vec<MultiFactorEnrollment>>,
 }
 
 /// Request to update an existing user.
@@ -151,6 +325,17 @@ pub struct UpdateUserRequest {
     /// List of providers to unlink.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub delete_provider: Option<Vec<String>>,
+    /// A federated identity to link to the user.
+    #[serde(rename = "linkProviderUserInfo", skip_serializing_if = "Option::is_none")]
+    pub provider_to_link: Option<UserProvider>,
+    /// Replaces the user's second factors. Existing factors not listed (by `uid`) are
+    /// removed; an empty list removes all of them.
+    #[serde(
+        rename = "mfa",
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_mfa_update"
+    )]
+    pub multi_factor: Option<Vec<MultiFactorEnrollment>>,
 }
 
 /// Response from listing users.
@@ -163,9 +348,64 @@ pub struct ListUsersResponse {
     pub next_page_token: Option<String>,
 }
 
-/// Internal request to get account info.
+/// Identifies a user to look up with `FirebaseAuth::get_users`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UserIdentifier {
+    /// The user's UID.
+    Uid(String),
+    /// The user's email address.
+    Email(String),
+    /// The user's phone number, in E.164 format.
+    PhoneNumber(String),
+    /// The user's ID at a federated identity provider.
+    Provider {
+        /// The provider ID (e.g., `google.com`).
+        provider_id: String,
+        /// The user's ID at the provider.
+        provider_uid: String,
+    },
+}
+
+impl UserIdentifier {
+    /// Returns `true` if `user` is the user identified by this identifier.
+    pub fn matches(&self, user: &UserRecord) -> bool {
+        match self {
+            UserIdentifier::Uid(uid) => user.local_id == *uid,
+            UserIdentifier::Email(email) => user
+                .email
+                .as_deref()
+                .is_some_and(|e| e.eq_ignore_ascii_case(email)),
+            UserIdentifier::PhoneNumber(phone) => user.phone_number.as_deref() == Some(phone),
+            UserIdentifier::Provider {
+                provider_id,
+                provider_uid,
+            } => user.provider_user_info.iter().flatten().any(|info| {
+                info.provider_id == *provider_id && info.raw_id.as_deref() == Some(provider_uid)
+            }),
+        }
+    }
+}
+
+/// The result of `FirebaseAuth::get_users`.
+#[derive(Debug, Default)]
+pub struct GetUsersResult {
+    /// The users that were found.
+    pub users: Vec<UserRecord>,
+    /// The identifiers that did not match any user.
+    pub not_found: Vec<UserIdentifier>,
+}
+
+/// Internal federated user identifier for account lookups.
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
+pub struct FederatedUserIdentifier {
+    pub provider_id: String,
+    pub raw_id: String,
+}
+
+/// Internal request to get account info.
+#[derive(Debug, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
 pub struct GetAccountInfoRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub local_id: Option<Vec<String>>,
@@ -173,6 +413,8 @@ pub struct GetAccountInfoRequest {
     pub email: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub phone_number: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub federated_user_id: Option<Vec<FederatedUserIdentifier>>,
 }
 
 /// Internal response from getting account info.
@@ -189,6 +431,45 @@ pub struct DeleteAccountRequest {
     pub local_id: String,
 }
 
+/// Internal request to delete several accounts.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchDeleteAccountsRequest {
+    pub local_ids: Vec<String>,
+    pub force: bool,
+}
+
+/// Internal response from deleting several accounts.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchDeleteAccountsResponse {
+    pub errors: Option<Vec<DeleteUserError>>,
+}
+
+/// The result of `FirebaseAuth::delete_users`.
+#[derive(Debug, Default)]
+pub struct DeleteUsersResult {
+    /// The number of users that were deleted.
+    pub success_count: usize,
+    /// The number of users that could not be deleted.
+    pub failure_count: usize,
+    /// The errors for the users that could not be deleted.
+    pub errors: Vec<DeleteUserError>,
+}
+
+/// Error detail for a user that could not be deleted.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteUserError {
+    /// The index of the uid in the list passed to `delete_users`.
+    #[serde(default)]
+    pub index: usize,
+    /// The uid of the user.
+    pub local_id: Option<String>,
+    /// The error message.
+    pub message: String,
+}
+
 /// Internal request for email actions.
 #[derive(Debug, Serialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -197,12 +478,17 @@ pub struct EmailLinkRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub email: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_email: Option<String>,
+    pub return_oob_link: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub continue_url: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub can_handle_code_in_app: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub dynamic_link_domain: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub link_domain: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub android_package_name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub android_minimum_version: Option<String>,
@@ -217,7 +503,8 @@ pub struct EmailLinkRequest {
 #[serde(rename_all = "camelCase")]
 pub struct EmailLinkResponse {
     pub email: Option<String>,
-    pub oob_link: String,
+    /// Only returned when `return_oob_link` was set.
+    pub oob_link: Option<String>,
 }
 
 /// A user record used for bulk import.
@@ -232,12 +519,12 @@ pub struct UserImportRecord {
     /// Whether the user's email is verified.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub email_verified: Option<bool>,
-    /// The user's password hash.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub password_hash: Option<String>,
-    /// The user's password salt.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub password_salt: Option<String>,
+    /// The user's password hash, as produced by the algorithm of the request's `hash`.
+    #[serde(skip_serializing_if = "Option::is_none", serialize_with = "serialize_base64")]
+    pub password_hash: Option<Vec<u8>>,
+    /// The salt used to hash this user's password.
+    #[serde(skip_serializing_if = "Option::is_none", serialize_with = "serialize_base64")]
+    pub password_salt: Option<Vec<u8>>,
     /// The user's display name.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub display_name: Option<String>,
@@ -261,28 +548,331 @@ pub struct UserImportRecord {
 pub struct ImportUsersRequest {
     /// The list of users to import.
     pub users: Vec<UserImportRecord>,
-    /// The hashing algorithm used for passwords (if any).
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// The algorithm used to hash the users' passwords. Required if any user has a
+    /// `password_hash`.
+    #[serde(flatten)]
     pub hash: Option<UserImportHash>,
 }
 
-/// Password hashing configuration for user import.
+/// Request to the `accounts:batchCreate` endpoint, for one batch of users.
 #[derive(Debug, Serialize)]
+pub struct BatchCreateAccountsRequest<'a> {
+    pub users: &'a [UserImportRecord],
+    #[serde(flatten)]
+    pub hash: Option<&'a UserImportHash>,
+}
+
+/// The algorithm, and its parameters, used to hash the passwords of imported users.
+///
+/// Use the constructors (e.g., [`UserImportHash::scrypt`]), which validate the parameters.
+/// Keys are given as raw bytes and base64-encoded when sent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UserImportHash {
+    /// HMAC-SHA512 with the given key.
+    HmacSha512 { key: Vec<u8> },
+    /// HMAC-SHA256 with the given key.
+    HmacSha256 { key: Vec<u8> },
+    /// HMAC-SHA1 with the given key.
+    HmacSha1 { key: Vec<u8> },
+    /// HMAC-MD5 with the given key.
+    HmacMd5 { key: Vec<u8> },
+    /// MD5 applied `rounds` times.
+    Md5 { rounds: u32 },
+    /// SHA-1 applied `rounds` times.
+    Sha1 { rounds: u32 },
+    /// SHA-256 applied `rounds` times.
+    Sha256 { rounds: u32 },
+    /// SHA-512 applied `rounds` times.
+    Sha512 { rounds: u32 },
+    /// PBKDF with SHA-1 and the given number of iterations.
+    PbkdfSha1 { rounds: u32 },
+    /// PBKDF2 with SHA-256 and the given number of iterations.
+    Pbkdf2Sha256 { rounds: u32 },
+    /// Firebase's modified scrypt, with the parameters shown in the Firebase console.
+    Scrypt {
+        key: Vec<u8>,
+        salt_separator: Option<Vec<u8>>,
+        rounds: u32,
+        memory_cost: u32,
+    },
+    /// Standard scrypt.
+    StandardScrypt {
+        memory_cost: u32,
+        parallelization: u32,
+        block_size: u32,
+        derived_key_length: u32,
+    },
+    /// bcrypt. The salt and cost are part of each password hash.
+    Bcrypt,
+}
+
+impl UserImportHash {
+    /// HMAC-SHA512 with the given key.
+    pub fn hmac_sha512(key: impl Into<Vec<u8>>) -> Result<Self, AuthError> {
+        Self::HmacSha512 { key: key.into() }.validated()
+    }
+
+    /// HMAC-SHA256 with the given key.
+    pub fn hmac_sha256(key: impl Into<Vec<u8>>) -> Result<Self, AuthError> {
+        Self::HmacSha256 { key: key.into() }.validated()
+    }
+
+    /// HMAC-SHA1 with the given key.
+    pub fn hmac_sha1(key: impl Into<Vec<u8>>) -> Result<Self, AuthError> {
+        Self::HmacSha1 { key: key.into() }.validated()
+    }
+
+    /// HMAC-MD5 with the given key.
+    pub fn hmac_md5(key: impl Into<Vec<u8>>) -> Result<Self, AuthError> {
+        Self::HmacMd5 { key: key.into() }.validated()
+    }
+
+    /// MD5 applied `rounds` (0 to 8192) times.
+    pub fn md5(rounds: u32) -> Result<Self, AuthError> {
+        Self::Md5 { rounds }.validated()
+    }
+
+    /// SHA-1 applied `rounds` (1 to 8192) times.
+    pub fn sha1(rounds: u32) -> Result<Self, AuthError> {
+        Self::Sha1 { rounds }.validated()
+    }
+
+    /// SHA-256 applied `rounds` (1 to 8192) times.
+    pub fn sha256(rounds: u32) -> Result<Self, AuthError> {
+        Self::Sha256 { rounds }.validated()
+    }
+
+    /// SHA-512 applied `rounds` (1 to 8192) times.
+    pub fn sha512(rounds: u32) -> Result<Self, AuthError> {
+        Self::Sha512 { rounds }.validated()
+    }
+
+    /// PBKDF with SHA-1 and `rounds` (0 to 120000) iterations.
+    pub fn pbkdf_sha1(rounds: u32) -> Result<Self, AuthError> {
+        Self::PbkdfSha1 { rounds }.validated()
+    }
+
+    /// PBKDF2 with SHA-256 and `rounds` (0 to 120000) iterations.
+    pub fn pbkdf2_sha256(rounds: u32) -> Result<Self, AuthError> {
+        Self::Pbkdf2Sha256 { rounds }.validated()
+    }
+
+    /// Firebase's modified scrypt, with `rounds` from 1 to 8 and `memory_cost` from 1 to 14.
+    pub fn scrypt(
+        key: impl Into<Vec<u8>>,
+        salt_separator: Option<Vec<u8>>,
+        rounds: u32,
+        memory_cost: u32,
+    ) -> Result<Self, AuthError> {
+        Self::Scrypt {
+            key: key.into(),
+            salt_separator,
+            rounds,
+            memory_cost,
+        }
+        .validated()
+    }
+
+    /// Standard scrypt. `memory_cost` is the CPU/memory cost (N) and `block_size` the block
+    /// size (r); all parameters must be positive.
+    pub fn standard_scrypt(
+        memory_cost: u32,
+        parallelization: u32,
+        block_size: u32,
+        derived_key_length: u32,
+    ) -> Result<Self, AuthError> {
+        Self::StandardScrypt {
+            memory_cost,
+            parallelization,
+            block_size,
+            derived_key_length,
+        }
+        .validated()
+    }
+
+    /// bcrypt.
+    pub fn bcrypt() -> Self {
+        Self::Bcrypt
+    }
+
+    /// The algorithm name expected by the API.
+    pub fn algorithm(&self) -> &'static str {
+        match self {
+            Self::HmacSha512 { .. } => "HMAC_SHA512",
+            Self::HmacSha256 { .. } => "HMAC_SHA256",
+            Self::HmacSha1 { .. } => "HMAC_SHA1",
+            Self::HmacMd5 { .. } => "HMAC_MD5",
+            Self::Md5 { .. } => "MD5",
+            Self::Sha1 { .. } => "SHA1",
+            Self::Sha256 { .. } => "SHA256",
+            Self::Sha512 { .. } => "SHA512",
+            Self::PbkdfSha1 { .. } => "PBKDF_SHA1",
+            Self::Pbkdf2Sha256 { .. } => "PBKDF2_SHA256",
+            Self::Scrypt { .. } => "SCRYPT",
+            Self::StandardScrypt { .. } => "STANDARD_SCRYPT",
+            Self::Bcrypt => "BCRYPT",
+        }
+    }
+
+    /// Checks the parameters against the ranges accepted by the API.
+    pub fn validate(&self) -> Result<(), AuthError> {
+        let check_rounds = |rounds: u32, min: u32, max: u32| {
+            if (min..=max).contains(&rounds) {
+                Ok(())
+            } else {
+                Err(AuthError::InvalidArgument(format!(
+                    "{} rounds must be between {} and {}",
+                    self.algorithm(),
+                    min,
+                    max
+                )))
+            }
+        };
+        let check_key = |key: &[u8]| {
+            if key.is_empty() {
+                Err(AuthError::InvalidArgument(format!(
+                    "{} requires a non-empty key",
+                    self.algorithm()
+                )))
+            } else {
+                Ok(())
+            }
+        };
+
+        match self {
+            Self::HmacSha512 { key }
+            | Self::HmacSha256 { key }
+            | Self::HmacSha1 { key }
+            | Self::HmacMd5 { key } => check_key(key),
+            Self::Md5 { rounds } => check_rounds(*rounds, 0, 8192),
+            Self::Sha1 { rounds } | Self::Sha256 { rounds } | Self::Sha512 { rounds } => {
+                check_rounds(*rounds, 1, 8192)
+            }
+            Self::PbkdfSha1 { rounds } | Self::Pbkdf2Sha256 { rounds } => {
+                check_rounds(*rounds, 0, 120_000)
+            }
+            Self::Scrypt {
+                key,
+                rounds,
+                memory_cost,
+                ..
+            } => {
+                check_key(key)?;
+                check_rounds(*rounds, 1, 8)?;
+                if !(1..=14).contains(memory_cost) {
+                    return Err(AuthError::InvalidArgument(
+                        "SCRYPT memory cost must be between 1 and 14".to_string(),
+                    ));
+                }
+                Ok(())
+            }
+            Self::StandardScrypt {
+                memory_cost,
+                parallelization,
+                block_size,
+                derived_key_length,
+            } => {
+                if [*memory_cost, *parallelization, *block_size, *derived_key_length].contains(&0) {
+                    return Err(AuthError::InvalidArgument(
+                        "STANDARD_SCRYPT parameters must be positive".to_string(),
+                    ));
+                }
+                Ok(())
+            }
+            Self::Bcrypt => Ok(()),
+        }
+    }
+
+    fn validated(self) -> Result<Self, AuthError> {
+        self.validate()?;
+        Ok(self)
+    }
+}
+
+/// The hash options of a `accounts:batchCreate` request.
+#[derive(Serialize, Default)]
 #[serde(rename_all = "camelCase")]
-pub struct UserImportHash {
-    /// The hashing algorithm (e.g., "SCRYPT").
-    pub hash_algorithm: String,
-    /// The signing key (base64 encoded).
-    pub key: String,
-    /// The salt separator (base64 encoded).
-    pub salt_separator: String,
-    /// The number of rounds.
-    pub rounds: i32,
-    /// The memory cost.
-    pub memory_cost: i32,
-}
-
-/// Response from user import.
+struct HashOptions<'a> {
+    hash_algorithm: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none", serialize_with = "serialize_base64")]
+    signer_key: Option<&'a [u8]>,
+    #[serde(skip_serializing_if = "Option::is_none", serialize_with = "serialize_base64")]
+    salt_separator: Option<&'a [u8]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rounds: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    memory_cost: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cpu_mem_cost: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parallelization: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    block_size: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dk_len: Option<u32>,
+}
+
+impl Serialize for UserImportHash {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut options = HashOptions {
+            hash_algorithm: self.algorithm(),
+            ..Default::default()
+        };
+        match self {
+            Self::HmacSha512 { key }
+            | Self::HmacSha256 { key }
+            | Self::HmacSha1 { key }
+            | Self::HmacMd5 { key } => options.signer_key = Some(key),
+            Self::Md5 { rounds }
+            | Self::Sha1 { rounds }
+            | Self::Sha256 { rounds }
+            | Self::Sha512 { rounds }
+            | Self::PbkdfSha1 { rounds }
+            | Self::Pbkdf2Sha256 { rounds } => options.rounds = Some(*rounds),
+            Self::Scrypt {
+                key,
+                salt_separator,
+                rounds,
+                memory_cost,
+            } => {
+                options.signer_key = Some(key);
+                options.salt_separator = salt_separator.as_deref();
+                options.rounds = Some(*rounds);
+                options.memory_cost = Some(*memory_cost);
+            }
+            Self::StandardScrypt {
+                memory_cost,
+                parallelization,
+                block_size,
+                derived_key_length,
+            } => {
+                options.cpu_mem_cost = Some(*memory_cost);
+                options.parallelization = Some(*parallelization);
+                options.block_size = Some(*block_size);
+                options.dk_len = Some(*derived_key_length);
+            }
+            Self::Bcrypt => {}
+        }
+        options.serialize(serializer)
+    }
+}
+
+/// Serializes bytes as web-safe base64, as expected by the Identity Toolkit API.
+fn serialize_base64<S, T>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+    T: AsRef<[u8]>,
+{
+    use base64::Engine;
+
+    match value {
+        Some(bytes) => serializer
+            .serialize_str(&base64::engine::general_purpose::URL_SAFE.encode(bytes.as_ref())),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Response from the `accounts:batchCreate` endpoint.
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ImportUsersResponse {
@@ -290,11 +880,23 @@ pub struct ImportUsersResponse {
     pub error: Option<Vec<ImportUserError>>,
 }
 
+/// The result of `FirebaseAuth::import_users`.
+#[derive(Debug, Default)]
+pub struct UserImportResult {
+    /// The number of users that were imported.
+    pub success_count: usize,
+    /// The number of users that could not be imported.
+    pub failure_count: usize,
+    /// The errors for the users that could not be imported.
+    pub errors: Vec<ImportUserError>,
+}
+
 /// Error detail for a failed user import.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ImportUserError {
-    /// The index of the user in the request list.
+    /// The index of the user in the `users` list of the request.
+    #[serde(default)]
     pub index: usize,
     /// The error message.
     pub message: String,
@@ -337,8 +939,14 @@ pub struct ActionCodeSettings {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub android: Option<AndroidSettings>,
     /// The dynamic link domain to use.
+    ///
+    /// Firebase Dynamic Links is shut down; use `link_domain` instead.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub dynamic_link_domain: Option<String>,
+    /// The Firebase Hosting domain (e.g., `custom.example.com` or `my-app.firebaseapp.com`)
+    /// used to open the link in a mobile app.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link_domain: Option<String>,
 }
 
 /// iOS specific settings for action code.
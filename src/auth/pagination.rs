@@ -0,0 +1,101 @@
+//! Streams over paginated list endpoints.
+
+use crate::auth::AuthError;
+use futures::stream::{self, Stream, StreamExt};
+use std::future::Future;
+
+/// Options for streaming list methods such as `FirebaseAuth::list_all_users`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ListOptions {
+    /// The number of results requested per page. Defaults to the maximum supported by the API.
+    pub page_size: Option<u32>,
+    /// The maximum number of pages fetched ahead of the consumer, while it is still processing
+    /// earlier results.
+    ///
+    /// Pages are linked by page tokens, so at most one request is in flight at a time. With
+    /// `None`, the next page is only requested once the current one has been consumed.
+    pub concurrency: Option<usize>,
+}
+
+impl ListOptions {
+    /// Sets the number of results requested per page.
+    pub fn with_page_size(mut self, page_size: u32) -> Self {
+        self.page_size = Some(page_size);
+        self
+    }
+
+    /// Sets the maximum number of pages fetched ahead of the consumer.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = Some(concurrency);
+        self
+    }
+}
+
+/// A page of results and the token of the next page, if any.
+pub(crate) type Page<T> = (Vec<T>, Option<String>);
+
+/// Turns a page fetcher into a stream of items that follows page tokens until the last page.
+///
+/// `fetch` receives the page token (`None` for the first page). The stream ends after the
+/// first error.
+pub(crate) fn paginate<T, F, Fut>(
+    options: ListOptions,
+    fetch: F,
+) -> impl Stream<Item = Result<T, AuthError>> + Send + 'static
+where
+    T: Send + 'static,
+    F: Fn(Option<String>) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<Page<T>, AuthError>> + Send + 'static,
+{
+    // `Some(token)` while there are pages left to fetch.
+    let pages = stream::unfold(Some(None), move |state: Option<Option<String>>| {
+        let next = state.map(&fetch);
+        async move {
+            match next?.await {
+                Ok((items, token)) => {
+                    let token = token.filter(|t| !t.is_empty());
+                    let state = token.map(Some);
+                    Some((Ok(items), state))
+                }
+                Err(e) => Some((Err(e), None)),
+            }
+        }
+    });
+
+    let pages = match options.concurrency {
+        Some(concurrency) => prefetch(pages, concurrency.max(1)).boxed(),
+        None => pages.boxed(),
+    };
+
+    pages.flat_map(|page| {
+        let items: Vec<Result<T, AuthError>> = match page {
+            Ok(items) => items.into_iter().map(Ok).collect(),
+            Err(e) => vec![Err(e)],
+        };
+        stream::iter(items)
+    })
+}
+
+/// Drives `pages` on a background task, buffering up to `capacity` pages.
+///
+/// The task is spawned on first poll, so the stream can be created outside of a runtime.
+/// It stops when the returned stream is dropped.
+fn prefetch<T, S>(pages: S, capacity: usize) -> impl Stream<Item = T> + Send + 'static
+where
+    T: Send + 'static,
+    S: Stream<Item = T> + Send + 'static,
+{
+    stream::once(async move {
+        let (tx, rx) = tokio::sync::mpsc::channel(capacity);
+        tokio::spawn(async move {
+            let mut pages = std::pin::pin!(pages);
+            while let Some(page) = pages.next().await {
+                if tx.send(page).await.is_err() {
+                    break;
+                }
+            }
+        });
+        stream::unfold(rx, |mut rx| async move { rx.recv().await.map(|page| (page, rx)) })
+    })
+    .flatten()
+}
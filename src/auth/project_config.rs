@@ -1,7 +1,9 @@
+use crate::auth::AuthError;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Represents an OIDC Provider Configuration.
-#[derive(Debug, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct OidcProviderConfig {
     /// The resource name of the config.
@@ -30,7 +32,7 @@ pub struct OidcProviderConfig {
     pub response_type: Option<OidcResponseType>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct OidcResponseType {
     /// Whether the ID token is requested.
@@ -79,6 +81,33 @@ pub struct UpdateOidcProviderConfigRequest {
     pub response_type: Option<OidcResponseType>,
 }
 
+impl UpdateOidcProviderConfigRequest {
+    /// Builds a request that changes `current` into `desired`.
+    ///
+    /// Only fields that are set in `desired` and differ from `current` are included; fields
+    /// that are `None` in `desired` are left unchanged.
+    pub fn diff(current: &OidcProviderConfig, desired: &OidcProviderConfig) -> Self {
+        Self {
+            display_name: changed(&current.display_name, &desired.display_name),
+            enabled: changed(&current.enabled, &desired.enabled),
+            client_id: changed(&current.client_id, &desired.client_id),
+            issuer: changed(&current.issuer, &desired.issuer),
+            client_secret: changed(&current.client_secret, &desired.client_secret),
+            response_type: changed(&current.response_type, &desired.response_type),
+        }
+    }
+
+    /// Returns `true` if the request changes nothing.
+    pub fn is_empty(&self) -> bool {
+        self.display_name.is_none()
+            && self.enabled.is_none()
+            && self.client_id.is_none()
+            && self.issuer.is_none()
+            && self.client_secret.is_none()
+            && self.response_type.is_none()
+    }
+}
+
 /// Response from listing OIDC Provider Configs.
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -89,7 +118,7 @@ pub struct ListOidcProviderConfigsResponse {
 
 // --- SAML Structures ---
 
-#[derive(Debug, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct SamlIdpConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -102,7 +131,7 @@ pub struct SamlIdpConfig {
     pub idp_certificates: Option<Vec<SamlCertificate>>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct SamlSpConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -111,14 +140,14 @@ pub struct SamlSpConfig {
     pub callback_uri: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct SamlCertificate {
     pub x509_certificate: String,
 }
 
 /// Represents a SAML Provider Configuration.
-#[derive(Debug, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct SamlProviderConfig {
     /// The resource name of the config.
@@ -168,6 +197,47 @@ pub struct UpdateSamlProviderConfigRequest {
     pub sp_config: Option<SamlSpConfig>,
 }
 
+impl UpdateSamlProviderConfigRequest {
+    /// Builds a request that changes `current` into `desired`.
+    ///
+    /// Only fields that are set in `desired` and differ from `current` are included, down to
+    /// the individual IdP and SP fields; fields that are `None` in `desired` are left
+    /// unchanged.
+    pub fn diff(current: &SamlProviderConfig, desired: &SamlProviderConfig) -> Self {
+        let idp_config = desired.idp_config.as_ref().map(|desired_idp| {
+            let current_idp = current.idp_config.clone().unwrap_or_default();
+            SamlIdpConfig {
+                idp_entity_id: changed(&current_idp.idp_entity_id, &desired_idp.idp_entity_id),
+                sso_url: changed(&current_idp.sso_url, &desired_idp.sso_url),
+                sign_request: changed(&current_idp.sign_request, &desired_idp.sign_request),
+                idp_certificates: changed(&current_idp.idp_certificates, &desired_idp.idp_certificates),
+            }
+        });
+        let sp_config = desired.sp_config.as_ref().map(|desired_sp| {
+            let current_sp = current.sp_config.clone().unwrap_or_default();
+            SamlSpConfig {
+                sp_entity_id: changed(&current_sp.sp_entity_id, &desired_sp.sp_entity_id),
+                callback_uri: changed(&current_sp.callback_uri, &desired_sp.callback_uri),
+            }
+        });
+
+        Self {
+            display_name: changed(&current.display_name, &desired.display_name),
+            enabled: changed(&current.enabled, &desired.enabled),
+            idp_config: idp_config.filter(|idp| *idp != SamlIdpConfig::default()),
+            sp_config: sp_config.filter(|sp| *sp != SamlSpConfig::default()),
+        }
+    }
+
+    /// Returns `true` if the request changes nothing.
+    pub fn is_empty(&self) -> bool {
+        self.display_name.is_none()
+            && self.enabled.is_none()
+            && self.idp_config.is_none()
+            && self.sp_config.is_none()
+    }
+}
+
 /// Response from listing SAML Provider Configs.
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -175,3 +245,536 @@ pub struct ListSamlProviderConfigsResponse {
     pub inbound_saml_configs: Option<Vec<SamlProviderConfig>>,
     pub next_page_token: Option<String>,
 }
+
+// --- Project Config Structures ---
+
+/// The state of multi-factor authentication.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum MultiFactorState {
+    /// Users cannot enroll second factors.
+    Disabled,
+    /// Users can enroll second factors.
+    Enabled,
+    /// Users must enroll a second factor.
+    Mandatory,
+    /// A state not known to this SDK.
+    #[serde(rename = "STATE_UNSPECIFIED", other)]
+    Unspecified,
+}
+
+/// A second factor provider that can be enabled in `MultiFactorConfig::enabled_providers`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum MultiFactorProvider {
+    /// SMS based second factors.
+    PhoneSms,
+    /// A provider not known to this SDK.
+    #[serde(rename = "PROVIDER_UNSPECIFIED", other)]
+    Unspecified,
+}
+
+/// Multi-factor authentication settings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiFactorConfig {
+    /// Whether second factors can or must be enrolled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<MultiFactorState>,
+    /// The SMS based providers that are enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled_providers: Option<Vec<MultiFactorProvider>>,
+    /// Settings of non-SMS providers, such as TOTP.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider_configs: Option<Vec<MultiFactorProviderConfig>>,
+}
+
+impl MultiFactorConfig {
+    pub(crate) fn validate(&self) -> Result<(), AuthError> {
+        for config in self.provider_configs.iter().flatten() {
+            let adjacent_intervals = config
+                .totp_provider_config
+                .as_ref()
+                .and_then(|totp| totp.adjacent_intervals);
+            if let Some(intervals) = adjacent_intervals {
+                if intervals > MAX_ADJACENT_INTERVALS {
+                    return Err(AuthError::InvalidArgument(format!(
+                        "TOTP adjacent intervals must be between 0 and {}",
+                        MAX_ADJACENT_INTERVALS
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The largest number of adjacent TOTP intervals accepted around the current one.
+const MAX_ADJACENT_INTERVALS: u32 = 10;
+
+/// Settings of a non-SMS second factor provider.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiFactorProviderConfig {
+    /// Whether the provider is enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<MultiFactorState>,
+    /// TOTP settings.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub totp_provider_config: Option<TotpProviderConfig>,
+}
+
+/// TOTP second factor settings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TotpProviderConfig {
+    /// The number of intervals before and after the current one in which codes are still
+    /// accepted (0 to 10).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub adjacent_intervals: Option<u32>,
+}
+
+/// Whether a password policy is enforced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PasswordPolicyEnforcementState {
+    /// The policy is not enforced.
+    Off,
+    /// New passwords must satisfy the policy.
+    Enforce,
+    /// A state not known to this SDK.
+    #[serde(rename = "ENFORCEMENT_STATE_UNSPECIFIED", other)]
+    Unspecified,
+}
+
+/// Password policy settings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PasswordPolicyConfig {
+    /// Whether the policy is enforced.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password_policy_enforcement_state: Option<PasswordPolicyEnforcementState>,
+    /// Whether users with non-compliant passwords must change them when signing in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub force_upgrade_on_signin: Option<bool>,
+    /// The policy; only one version is supported.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password_policy_versions: Option<Vec<PasswordPolicyVersion>>,
+    /// When the policy was last updated. Output only, so it is never sent in updates.
+    #[serde(skip_serializing)]
+    pub last_update_time: Option<String>,
+}
+
+impl PasswordPolicyConfig {
+    /// Returns a copy without the output-only fields, for comparing configs.
+    pub(crate) fn without_output_only(&self) -> Self {
+        Self {
+            password_policy_versions: self.password_policy_versions.as_ref().map(|versions| {
+                versions
+                    .iter()
+                    .map(|version| PasswordPolicyVersion {
+                        schema_version: None,
+                        ..version.clone()
+                    })
+                    .collect()
+            }),
+            last_update_time: None,
+            ..self.clone()
+        }
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), AuthError> {
+        let options = self
+            .password_policy_versions
+            .iter()
+            .flatten()
+            .filter_map(|version| version.custom_strength_options.as_ref());
+        for options in options {
+            let min = options.min_password_length.unwrap_or(MIN_PASSWORD_LENGTH);
+            if !(MIN_PASSWORD_LENGTH..=MAX_MIN_PASSWORD_LENGTH).contains(&min) {
+                return Err(AuthError::InvalidArgument(format!(
+                    "Minimum password length must be between {} and {}",
+                    MIN_PASSWORD_LENGTH, MAX_MIN_PASSWORD_LENGTH
+                )));
+            }
+            if let Some(max) = options.max_password_length {
+                if max < min || max > MAX_PASSWORD_LENGTH {
+                    return Err(AuthError::InvalidArgument(format!(
+                        "Maximum password length must be between the minimum length and {}",
+                        MAX_PASSWORD_LENGTH
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+const MIN_PASSWORD_LENGTH: u32 = 6;
+const MAX_MIN_PASSWORD_LENGTH: u32 = 30;
+const MAX_PASSWORD_LENGTH: u32 = 4096;
+
+/// A version of a password policy.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PasswordPolicyVersion {
+    /// The password requirements.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub custom_strength_options: Option<CustomStrengthOptions>,
+    /// The schema version of the policy. Output only, so it is never sent in updates.
+    #[serde(skip_serializing)]
+    pub schema_version: Option<u32>,
+}
+
+/// The requirements passwords must meet.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomStrengthOptions {
+    /// The minimum length (6 to 30, defaults to 6).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_password_length: Option<u32>,
+    /// The maximum length (up to 4096).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_password_length: Option<u32>,
+    /// Whether a lowercase character is required.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contains_lowercase_character: Option<bool>,
+    /// Whether an uppercase character is required.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contains_uppercase_character: Option<bool>,
+    /// Whether a numeric character is required.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contains_numeric_character: Option<bool>,
+    /// Whether a non-alphanumeric character is required.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contains_non_alphanumeric_character: Option<bool>,
+}
+
+/// Email privacy settings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct EmailPrivacyConfig {
+    /// Whether improved email privacy (protection against email enumeration) is enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enable_improved_email_privacy: Option<bool>,
+}
+
+/// How reCAPTCHA Enterprise assessments are enforced for a sign-in method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RecaptchaEnforcementState {
+    /// No assessments are made.
+    Off,
+    /// Assessments are made but requests are not blocked.
+    Audit,
+    /// Requests are blocked according to the managed rules.
+    Enforce,
+    /// A state not known to this SDK.
+    #[serde(rename = "RECAPTCHA_PROVIDER_ENFORCEMENT_STATE_UNSPECIFIED", other)]
+    Unspecified,
+}
+
+/// The action taken when a reCAPTCHA managed rule matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RecaptchaAction {
+    /// The request is blocked.
+    Block,
+    /// An action not known to this SDK.
+    #[serde(rename = "RECAPTCHA_ACTION_UNSPECIFIED", other)]
+    Unspecified,
+}
+
+/// Blocks requests whose reCAPTCHA score is at most `end_score`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct RecaptchaManagedRule {
+    /// The score threshold, between 0.0 and 1.0.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_score: Option<f64>,
+    /// The action taken.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action: Option<RecaptchaAction>,
+}
+
+/// The platform of a reCAPTCHA Enterprise site key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RecaptchaKeyType {
+    /// A key for websites.
+    Web,
+    /// A key for iOS apps.
+    Ios,
+    /// A key for Android apps.
+    Android,
+    /// A platform not known to this SDK.
+    #[serde(rename = "CLIENT_TYPE_UNSPECIFIED", other)]
+    Unspecified,
+}
+
+/// A reCAPTCHA Enterprise site key provisioned for the project.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecaptchaKey {
+    /// The platform of the key.
+    #[serde(rename = "type")]
+    pub key_type: RecaptchaKeyType,
+    /// The resource name of the key.
+    pub key: String,
+}
+
+/// reCAPTCHA Enterprise settings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct RecaptchaConfig {
+    /// Enforcement for email/password sign-in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email_password_enforcement_state: Option<RecaptchaEnforcementState>,
+    /// Enforcement for phone sign-in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phone_enforcement_state: Option<RecaptchaEnforcementState>,
+    /// The rules applied when enforcing. Only one rule is supported.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub managed_rules: Option<Vec<RecaptchaManagedRule>>,
+    /// The provisioned site keys. Output only, so they are never sent in updates.
+    #[serde(skip_serializing)]
+    pub recaptcha_keys: Option<Vec<RecaptchaKey>>,
+    /// Whether reCAPTCHA account defender assessments are used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub use_account_defender: Option<bool>,
+    /// Whether the reCAPTCHA SMS defense bot score is used for phone sign-in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub use_sms_bot_score: Option<bool>,
+    /// Whether the reCAPTCHA SMS toll fraud protection score is used for phone sign-in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub use_sms_toll_fraud_protection: Option<bool>,
+}
+
+impl RecaptchaConfig {
+    /// Returns a copy without the output-only fields, for comparing configs.
+    pub(crate) fn without_output_only(&self) -> Self {
+        Self {
+            recaptcha_keys: None,
+            ..self.clone()
+        }
+    }
+}
+
+/// The regions to which SMS verification codes can be sent, as ISO 3166 region codes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum SmsRegionConfig {
+    /// All regions except the listed ones are allowed.
+    AllowByDefault {
+        #[serde(default)]
+        disallowed_regions: Vec<String>,
+    },
+    /// Only the listed regions are allowed.
+    AllowlistOnly {
+        #[serde(default)]
+        allowed_regions: Vec<String>,
+    },
+}
+
+/// Request logging settings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct MonitoringConfig {
+    /// Whether requests are logged to Cloud Logging.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_logging: Option<RequestLogging>,
+}
+
+/// Cloud Logging settings for Authentication requests.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestLogging {
+    /// Whether requests are logged.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+}
+
+/// Actions clients are allowed to take on behalf of users.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientPermissionConfig {
+    /// The permissions granted to clients.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub permissions: Option<ClientPermissions>,
+}
+
+/// Account actions that can be blocked for client SDKs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientPermissions {
+    /// Whether clients are blocked from signing up new users.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disabled_user_signup: Option<bool>,
+    /// Whether clients are blocked from deleting users.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disabled_user_deletion: Option<bool>,
+}
+
+/// The sign-in methods enabled for the project.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SignInConfig {
+    /// Email/password and email link sign-in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<EmailSignInConfig>,
+    /// Phone number sign-in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phone_number: Option<PhoneNumberSignInConfig>,
+    /// Anonymous sign-in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub anonymous: Option<AnonymousSignInConfig>,
+    /// Whether more than one account can have the same email.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_duplicate_emails: Option<bool>,
+}
+
+/// Email/password and email link sign-in settings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct EmailSignInConfig {
+    /// Whether email sign-in is enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    /// Whether a password is required; `false` enables email link sign-in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password_required: Option<bool>,
+}
+
+/// Phone number sign-in settings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PhoneNumberSignInConfig {
+    /// Whether phone number sign-in is enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    /// Map of test phone numbers and their fake verification codes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub test_phone_numbers: Option<HashMap<String, String>>,
+}
+
+/// Anonymous sign-in settings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct AnonymousSignInConfig {
+    /// Whether anonymous sign-in is enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+}
+
+/// The project-level Authentication configuration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectAuthConfig {
+    /// The resource name of the config.
+    /// Format: "projects/{project-id}/config"
+    #[serde(default)]
+    pub name: String,
+
+    /// The enabled sign-in methods.
+    pub sign_in: Option<SignInConfig>,
+
+    /// Multi-factor authentication settings.
+    pub mfa: Option<MultiFactorConfig>,
+
+    /// The regions SMS verification codes can be sent to.
+    pub sms_region_config: Option<SmsRegionConfig>,
+
+    /// reCAPTCHA Enterprise settings.
+    pub recaptcha_config: Option<RecaptchaConfig>,
+
+    /// The password policy.
+    pub password_policy_config: Option<PasswordPolicyConfig>,
+
+    /// Email privacy settings.
+    pub email_privacy_config: Option<EmailPrivacyConfig>,
+
+    /// Request logging settings.
+    pub monitoring: Option<MonitoringConfig>,
+}
+
+/// Request to update the project-level Authentication configuration.
+///
+/// Only the fields that are set are updated, down to individual nested fields.
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateProjectAuthConfigRequest {
+    /// The enabled sign-in methods.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sign_in: Option<SignInConfig>,
+    /// Multi-factor authentication settings.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mfa: Option<MultiFactorConfig>,
+    /// The regions SMS verification codes can be sent to. Replaced as a whole.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sms_region_config: Option<SmsRegionConfig>,
+    /// reCAPTCHA Enterprise settings.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recaptcha_config: Option<RecaptchaConfig>,
+    /// The password policy.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password_policy_config: Option<PasswordPolicyConfig>,
+    /// Email privacy settings.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email_privacy_config: Option<EmailPrivacyConfig>,
+    /// Request logging settings.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub monitoring: Option<MonitoringConfig>,
+}
+
+impl UpdateProjectAuthConfigRequest {
+    pub(crate) fn validate(&self) -> Result<(), AuthError> {
+        if let Some(mfa) = &self.mfa {
+            mfa.validate()?;
+        }
+        if let Some(policy) = &self.password_policy_config {
+            policy.validate()?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns `desired` if it is set and differs from `current`, for building update requests
+/// from the difference between two configs.
+pub(crate) fn changed<T: Clone + PartialEq>(current: &Option<T>, desired: &Option<T>) -> Option<T> {
+    desired.clone().filter(|value| current.as_ref() != Some(value))
+}
+
+/// Fields holding maps (e.g. `HashMap<String, String>`), whose keys are data rather than
+/// field names. They are replaced as a whole, wherever they appear.
+const MAP_FIELDS: &[&str] = &["testPhoneNumbers"];
+
+/// Builds an update mask with the paths of all fields set in `value`.
+///
+/// Objects are descended into, so unset nested fields are left untouched. Arrays, empty
+/// objects, map fields and the paths in `terminal_paths` (e.g. `oneof` fields, which must
+/// be replaced as a whole) are leaves.
+pub(crate) fn update_mask(value: &serde_json::Value, terminal_paths: &[&str]) -> String {
+    fn is_leaf(path: &str, terminal_paths: &[&str]) -> bool {
+        let field = path.rsplit('.').next().unwrap_or(path);
+        terminal_paths.contains(&path) || MAP_FIELDS.contains(&field)
+    }
+
+    fn collect(value: &serde_json::Value, path: String, terminal_paths: &[&str], mask: &mut Vec<String>) {
+        match value {
+            serde_json::Value::Object(fields)
+                if !fields.is_empty() && !is_leaf(&path, terminal_paths) =>
+            {
+                for (key, field) in fields {
+                    let path = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
+                    collect(field, path, terminal_paths, mask);
+                }
+            }
+            _ if path.is_empty() => {}
+            _ => mask.push(path),
+        }
+    }
+
+    let mut mask = Vec::new();
+    collect(value, String::new(), terminal_paths, &mut mask);
+    mask.join(",")
+}
@@ -1,43 +1,125 @@
-//! Project configuration management (OIDC, SAML).
+//! Project configuration management (Authentication settings, OIDC, SAML).
 
 use crate::auth::project_config::{
-    CreateOidcProviderConfigRequest, CreateSamlProviderConfigRequest,
+    update_mask, CreateOidcProviderConfigRequest, CreateSamlProviderConfigRequest,
     ListOidcProviderConfigsResponse, ListSamlProviderConfigsResponse, OidcProviderConfig,
-    SamlProviderConfig, UpdateOidcProviderConfigRequest, UpdateSamlProviderConfigRequest,
+    ProjectAuthConfig, SamlProviderConfig, UpdateOidcProviderConfigRequest,
+    UpdateProjectAuthConfigRequest, UpdateSamlProviderConfigRequest,
 };
-use crate::auth::AuthError;
+use crate::auth::pagination::{paginate, ListOptions};
+use crate::auth::{identity_toolkit_endpoint, AuthError, AUTH_V2_API, AUTH_V2_TENANT_API};
+use crate::core::http::build_client;
 use crate::core::middleware::AuthMiddleware;
-use reqwest::Client;
-use reqwest_middleware::{ClientBuilder, ClientWithMiddleware};
-use reqwest_retry::{policies::ExponentialBackoff, RetryTransientMiddleware};
+use crate::core::telemetry::Operation;
+use crate::core::FirebaseError;
+use futures::Stream;
+use reqwest_middleware::ClientWithMiddleware;
 use url::Url;
 
-const IDENTITY_TOOLKIT_URL: &str = "https://identitytoolkit.googleapis.com/v2";
-
-/// Manages project-level configurations like OIDC and SAML providers.
+/// Manages project-level configurations like Authentication settings and OIDC and SAML
+/// providers.
+///
+/// When scoped to a tenant (see `TenantAwareness::project_config_for_tenant`), the OIDC and
+/// SAML providers of that tenant are managed instead.
 #[derive(Clone)]
 pub struct ProjectConfig {
     client: ClientWithMiddleware,
     base_url: String,
+    tenant_id: Option<String>,
 }
 
 impl ProjectConfig {
     pub(crate) fn new(middleware: AuthMiddleware) -> Self {
-        let retry_policy = ExponentialBackoff::builder().build_with_max_retries(3);
-        let client = ClientBuilder::new(Client::new())
-            .with(RetryTransientMiddleware::new_with_policy(retry_policy))
-            .with(middleware.clone())
-            .build();
+        let (origin, middleware) = identity_toolkit_endpoint(middleware);
+        let client = build_client(&middleware, "auth");
+
+        let project_id = middleware.project_id().to_string();
+        let tenant_id = middleware.tenant_id();
+
+        let base_url = if let Some(tid) = &tenant_id {
+            AUTH_V2_TENANT_API.replace("{origin}", &origin).replace("{project_id}", &project_id).replace("{tenant_id}", tid)
+        } else {
+            AUTH_V2_API.replace("{origin}", &origin).replace("{project_id}", &project_id)
+        };
+
+        Self { client, base_url, tenant_id }
+    }
 
-        let project_id = middleware.key.project_id.clone().unwrap_or_default();
-        let base_url = format!("{}/projects/{}", IDENTITY_TOOLKIT_URL, project_id);
+    /// Creates a new `ProjectConfig` with a custom client and base URL
+    /// (e.g., `http://localhost:8080/v2/projects/my-project`, or
+    /// `http://localhost:8080/v2/projects/my-project/tenants/my-tenant` for a tenant's providers).
+    pub fn new_with_client(client: ClientWithMiddleware, base_url: String) -> Self {
+        Self {
+            client,
+            base_url,
+            tenant_id: None,
+        }
+    }
 
-        Self { client, base_url }
+    /// Returns the ID of the tenant this instance is scoped to, if any.
+    pub fn tenant_id(&self) -> Option<&str> {
+        self.tenant_id.as_deref()
     }
 
-    #[cfg(test)]
-    pub(crate) fn new_with_client(client: ClientWithMiddleware, base_url: String) -> Self {
-        Self { client, base_url }
+    /// Fails for tenant-scoped instances, whose settings live on the `Tenant` resource.
+    fn check_project_scope(&self) -> Result<(), AuthError> {
+        match &self.tenant_id {
+            Some(tenant_id) => Err(AuthError::InvalidArgument(format!(
+                "The Authentication config of tenant {} is managed with TenantAwareness::update_tenant",
+                tenant_id
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    // --- Authentication Config ---
+
+    /// Retrieves the project-level Authentication configuration.
+    pub async fn get_config(&self) -> Result<ProjectAuthConfig, AuthError> {
+        self.check_project_scope()?;
+        let url = format!("{}/config", self.base_url);
+
+        let response = self.client.get(&url).with_extension(Operation("get_project_config")).send().await?;
+
+        if !response.status().is_success() {
+            return Err(FirebaseError::from_response(response, "Get project config failed").await.into());
+        }
+
+        let config: ProjectAuthConfig = response.json().await?;
+        Ok(config)
+    }
+
+    /// Updates the project-level Authentication configuration.
+    ///
+    /// Only the fields set in `request` are changed. An `sms_region_config` replaces the
+    /// current region list as a whole.
+    pub async fn update_config(
+        &self,
+        request: UpdateProjectAuthConfigRequest,
+    ) -> Result<ProjectAuthConfig, AuthError> {
+        self.check_project_scope()?;
+        request.validate()?;
+
+        let url = format!("{}/config", self.base_url);
+        let update_mask = update_mask(&serde_json::to_value(&request)?, &["smsRegionConfig"]);
+
+        let mut url_obj = Url::parse(&url)?;
+        url_obj.query_pairs_mut().append_pair("updateMask", &update_mask);
+
+        let response = self
+            .client
+            .patch(url_obj)
+            .with_extension(Operation("update_project_config"))
+            .json(&request)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(FirebaseError::from_response(response, "Update project config failed").await.into());
+        }
+
+        let config: ProjectAuthConfig = response.json().await?;
+        Ok(config)
     }
 
     // --- OIDC Provider Configs ---
@@ -47,23 +129,19 @@ impl ProjectConfig {
         request: CreateOidcProviderConfigRequest,
     ) -> Result<OidcProviderConfig, AuthError> {
         let url = format!("{}/oauthIdpConfigs", self.base_url);
-        let mut url_obj = Url::parse(&url).map_err(|e| AuthError::ApiError(e.to_string()))?;
+        let mut url_obj = Url::parse(&url)?;
         url_obj.query_pairs_mut().append_pair("oauthIdpConfigId", &request.oauth_idp_config_id);
 
         let response = self
             .client
             .post(url_obj)
+            .with_extension(Operation("create_oidc_provider_config"))
             .json(&request)
             .send()
             .await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            return Err(AuthError::ApiError(format!(
-                "Create OIDC config failed {}: {}",
-                status, text
-            )));
+            return Err(FirebaseError::from_response(response, "Create OIDC config failed").await.into());
         }
 
         let config: OidcProviderConfig = response.json().await?;
@@ -76,15 +154,10 @@ impl ProjectConfig {
     ) -> Result<OidcProviderConfig, AuthError> {
         let url = format!("{}/oauthIdpConfigs/{}", self.base_url, config_id);
 
-        let response = self.client.get(&url).send().await?;
+        let response = self.client.get(&url).with_extension(Operation("get_oidc_provider_config")).send().await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            return Err(AuthError::ApiError(format!(
-                "Get OIDC config failed {}: {}",
-                status, text
-            )));
+            return Err(FirebaseError::from_response(response, "Get OIDC config failed").await.into());
         }
 
         let config: OidcProviderConfig = response.json().await?;
@@ -108,41 +181,66 @@ impl ProjectConfig {
 
         let update_mask = mask_parts.join(",");
 
-        let mut url_obj = Url::parse(&url).map_err(|e| AuthError::ApiError(e.to_string()))?;
+        let mut url_obj = Url::parse(&url)?;
         url_obj.query_pairs_mut().append_pair("updateMask", &update_mask);
 
         let response = self
             .client
             .patch(url_obj)
+            .with_extension(Operation("update_oidc_provider_config"))
             .json(&request)
             .send()
             .await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            return Err(AuthError::ApiError(format!(
-                "Update OIDC config failed {}: {}",
-                status, text
-            )));
+            return Err(FirebaseError::from_response(response, "Update OIDC config failed").await.into());
         }
 
         let config: OidcProviderConfig = response.json().await?;
         Ok(config)
     }
 
+    /// Creates or updates an OIDC provider configuration to match `desired`, sending only
+    /// the fields that changed.
+    ///
+    /// Fields that are `None` in `desired` are left unchanged. No update is sent if nothing
+    /// changed; the current configuration is returned instead.
+    pub async fn sync_oidc_provider_config(
+        &self,
+        config_id: &str,
+        desired: &OidcProviderConfig,
+    ) -> Result<OidcProviderConfig, AuthError> {
+        let current = match self.get_oidc_provider_config(config_id).await {
+            Ok(current) => current,
+            Err(AuthError::ConfigurationNotFound(_)) => {
+                let request = CreateOidcProviderConfigRequest {
+                    oauth_idp_config_id: config_id.to_string(),
+                    display_name: desired.display_name.clone(),
+                    enabled: desired.enabled,
+                    client_id: desired.client_id.clone().unwrap_or_default(),
+                    issuer: desired.issuer.clone().unwrap_or_default(),
+                    client_secret: desired.client_secret.clone(),
+                    response_type: desired.response_type.clone(),
+                };
+                return self.create_oidc_provider_config(request).await;
+            }
+            Err(e) => return Err(e),
+        };
+
+        let request = UpdateOidcProviderConfigRequest::diff(&current, desired);
+        if request.is_empty() {
+            return Ok(current);
+        }
+        self.update_oidc_provider_config(config_id, request).await
+    }
+
     pub async fn delete_oidc_provider_config(&self, config_id: &str) -> Result<(), AuthError> {
         let url = format!("{}/oauthIdpConfigs/{}", self.base_url, config_id);
 
-        let response = self.client.delete(&url).send().await?;
+        let response = self.client.delete(&url).with_extension(Operation("delete_oidc_provider_config")).send().await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            return Err(AuthError::ApiError(format!(
-                "Delete OIDC config failed {}: {}",
-                status, text
-            )));
+            return Err(FirebaseError::from_response(response, "Delete OIDC config failed").await.into());
         }
 
         Ok(())
@@ -154,7 +252,7 @@ impl ProjectConfig {
         page_token: Option<&str>,
     ) -> Result<ListOidcProviderConfigsResponse, AuthError> {
         let url = format!("{}/oauthIdpConfigs", self.base_url);
-        let mut url_obj = Url::parse(&url).map_err(|e| AuthError::ApiError(e.to_string()))?;
+        let mut url_obj = Url::parse(&url)?;
 
         {
             let mut query_pairs = url_obj.query_pairs_mut();
@@ -166,21 +264,34 @@ impl ProjectConfig {
             }
         }
 
-        let response = self.client.get(url_obj).send().await?;
+        let response = self.client.get(url_obj).with_extension(Operation("list_oidc_provider_configs")).send().await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            return Err(AuthError::ApiError(format!(
-                "List OIDC configs failed {}: {}",
-                status, text
-            )));
+            return Err(FirebaseError::from_response(response, "List OIDC configs failed").await.into());
         }
 
         let result: ListOidcProviderConfigsResponse = response.json().await?;
         Ok(result)
     }
 
+    /// Lists all OIDC provider configurations as a stream, following page tokens until the
+    /// last page.
+    pub fn list_all_oidc_provider_configs(
+        &self,
+        options: ListOptions,
+    ) -> impl Stream<Item = Result<OidcProviderConfig, AuthError>> + Send + 'static {
+        let config = self.clone();
+        paginate(options, move |page_token| {
+            let config = config.clone();
+            async move {
+                let page = config
+                    .list_oidc_provider_configs(options.page_size, page_token.as_deref())
+                    .await?;
+                Ok((page.oauth_idp_configs.unwrap_or_default(), page.next_page_token))
+            }
+        })
+    }
+
     // --- SAML Provider Configs ---
 
     pub async fn create_saml_provider_config(
@@ -188,23 +299,19 @@ impl ProjectConfig {
         request: CreateSamlProviderConfigRequest,
     ) -> Result<SamlProviderConfig, AuthError> {
         let url = format!("{}/inboundSamlConfigs", self.base_url);
-        let mut url_obj = Url::parse(&url).map_err(|e| AuthError::ApiError(e.to_string()))?;
+        let mut url_obj = Url::parse(&url)?;
         url_obj.query_pairs_mut().append_pair("inboundSamlConfigId", &request.inbound_saml_config_id);
 
         let response = self
             .client
             .post(url_obj)
+            .with_extension(Operation("create_saml_provider_config"))
             .json(&request)
             .send()
             .await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            return Err(AuthError::ApiError(format!(
-                "Create SAML config failed {}: {}",
-                status, text
-            )));
+            return Err(FirebaseError::from_response(response, "Create SAML config failed").await.into());
         }
 
         let config: SamlProviderConfig = response.json().await?;
@@ -217,15 +324,10 @@ impl ProjectConfig {
     ) -> Result<SamlProviderConfig, AuthError> {
         let url = format!("{}/inboundSamlConfigs/{}", self.base_url, config_id);
 
-        let response = self.client.get(&url).send().await?;
+        let response = self.client.get(&url).with_extension(Operation("get_saml_provider_config")).send().await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            return Err(AuthError::ApiError(format!(
-                "Get SAML config failed {}: {}",
-                status, text
-            )));
+            return Err(FirebaseError::from_response(response, "Get SAML config failed").await.into());
         }
 
         let config: SamlProviderConfig = response.json().await?;
@@ -258,41 +360,64 @@ impl ProjectConfig {
 
         let update_mask = mask_parts.join(",");
 
-        let mut url_obj = Url::parse(&url).map_err(|e| AuthError::ApiError(e.to_string()))?;
+        let mut url_obj = Url::parse(&url)?;
         url_obj.query_pairs_mut().append_pair("updateMask", &update_mask);
 
         let response = self
             .client
             .patch(url_obj)
+            .with_extension(Operation("update_saml_provider_config"))
             .json(&request)
             .send()
             .await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            return Err(AuthError::ApiError(format!(
-                "Update SAML config failed {}: {}",
-                status, text
-            )));
+            return Err(FirebaseError::from_response(response, "Update SAML config failed").await.into());
         }
 
         let config: SamlProviderConfig = response.json().await?;
         Ok(config)
     }
 
+    /// Creates or updates a SAML provider configuration to match `desired`, sending only
+    /// the fields that changed.
+    ///
+    /// Fields that are `None` in `desired` are left unchanged. No update is sent if nothing
+    /// changed; the current configuration is returned instead.
+    pub async fn sync_saml_provider_config(
+        &self,
+        config_id: &str,
+        desired: &SamlProviderConfig,
+    ) -> Result<SamlProviderConfig, AuthError> {
+        let current = match self.get_saml_provider_config(config_id).await {
+            Ok(current) => current,
+            Err(AuthError::ConfigurationNotFound(_)) => {
+                let request = CreateSamlProviderConfigRequest {
+                    inbound_saml_config_id: config_id.to_string(),
+                    display_name: desired.display_name.clone(),
+                    enabled: desired.enabled,
+                    idp_config: desired.idp_config.clone().unwrap_or_default(),
+                    sp_config: desired.sp_config.clone().unwrap_or_default(),
+                };
+                return self.create_saml_provider_config(request).await;
+            }
+            Err(e) => return Err(e),
+        };
+
+        let request = UpdateSamlProviderConfigRequest::diff(&current, desired);
+        if request.is_empty() {
+            return Ok(current);
+        }
+        self.update_saml_provider_config(config_id, request).await
+    }
+
     pub async fn delete_saml_provider_config(&self, config_id: &str) -> Result<(), AuthError> {
         let url = format!("{}/inboundSamlConfigs/{}", self.base_url, config_id);
 
-        let response = self.client.delete(&url).send().await?;
+        let response = self.client.delete(&url).with_extension(Operation("delete_saml_provider_config")).send().await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            return Err(AuthError::ApiError(format!(
-                "Delete SAML config failed {}: {}",
-                status, text
-            )));
+            return Err(FirebaseError::from_response(response, "Delete SAML config failed").await.into());
         }
 
         Ok(())
@@ -304,7 +429,7 @@ impl ProjectConfig {
         page_token: Option<&str>,
     ) -> Result<ListSamlProviderConfigsResponse, AuthError> {
         let url = format!("{}/inboundSamlConfigs", self.base_url);
-        let mut url_obj = Url::parse(&url).map_err(|e| AuthError::ApiError(e.to_string()))?;
+        let mut url_obj = Url::parse(&url)?;
 
         {
             let mut query_pairs = url_obj.query_pairs_mut();
@@ -316,18 +441,31 @@ impl ProjectConfig {
             }
         }
 
-        let response = self.client.get(url_obj).send().await?;
+        let response = self.client.get(url_obj).with_extension(Operation("list_saml_provider_configs")).send().await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            return Err(AuthError::ApiError(format!(
-                "List SAML configs failed {}: {}",
-                status, text
-            )));
+            return Err(FirebaseError::from_response(response, "List SAML configs failed").await.into());
         }
 
         let result: ListSamlProviderConfigsResponse = response.json().await?;
         Ok(result)
     }
+
+    /// Lists all SAML provider configurations as a stream, following page tokens until the
+    /// last page.
+    pub fn list_all_saml_provider_configs(
+        &self,
+        options: ListOptions,
+    ) -> impl Stream<Item = Result<SamlProviderConfig, AuthError>> + Send + 'static {
+        let config = self.clone();
+        paginate(options, move |page_token| {
+            let config = config.clone();
+            async move {
+                let page = config
+                    .list_saml_provider_configs(options.page_size, page_token.as_deref())
+                    .await?;
+                Ok((page.inbound_saml_configs.unwrap_or_default(), page.next_page_token))
+            }
+        })
+    }
 }
\ No newline at end of file
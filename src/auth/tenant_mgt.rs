@@ -1,17 +1,23 @@
 //! Tenant management module.
 
-use crate::auth::{AuthError, FirebaseAuth};
+use crate::auth::pagination::{paginate, ListOptions};
+use crate::auth::project_config::{
+    changed, ClientPermissionConfig, EmailPrivacyConfig, MonitoringConfig, MultiFactorConfig,
+    PasswordPolicyConfig, RecaptchaConfig, SmsRegionConfig,
+};
+use crate::auth::project_config_impl::ProjectConfig;
+use crate::auth::{identity_toolkit_endpoint, AuthError, FirebaseAuth};
+use crate::core::http::build_client;
 use crate::core::middleware::AuthMiddleware;
-use reqwest::Client;
-use reqwest_middleware::{ClientBuilder, ClientWithMiddleware};
-use reqwest_retry::{policies::ExponentialBackoff, RetryTransientMiddleware};
+use crate::core::telemetry::Operation;
+use crate::core::FirebaseError;
+use futures::Stream;
+use reqwest_middleware::ClientWithMiddleware;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
-const IDENTITY_TOOLKIT_URL: &str = "https://identitytoolkit.googleapis.com/v2";
-
 /// Represents a tenant in a multi-tenant project.
-#[derive(Debug, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct Tenant {
     /// The resource name of the tenant.
@@ -43,31 +49,31 @@ pub struct Tenant {
 
     /// The tenant-level configuration of MFA options.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub mfa_config: Option<serde_json::Value>,
+    pub mfa_config: Option<MultiFactorConfig>,
 
     /// The tenant-level reCAPTCHA config.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub recaptcha_config: Option<serde_json::Value>,
+    pub recaptcha_config: Option<RecaptchaConfig>,
 
     /// Configures which regions are enabled for SMS verification.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub sms_region_config: Option<serde_json::Value>,
+    pub sms_region_config: Option<SmsRegionConfig>,
 
     /// Configuration related to monitoring project activity.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub monitoring: Option<serde_json::Value>,
+    pub monitoring: Option<MonitoringConfig>,
 
     /// The tenant-level password policy config.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub password_policy_config: Option<serde_json::Value>,
+    pub password_policy_config: Option<PasswordPolicyConfig>,
 
     /// Configuration for settings related to email privacy.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub email_privacy_config: Option<serde_json::Value>,
+    pub email_privacy_config: Option<EmailPrivacyConfig>,
 
     /// Options related to how clients making requests on behalf of a project should be configured.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub client: Option<serde_json::Value>,
+    pub client: Option<ClientPermissionConfig>,
 }
 
 /// Request to create a new tenant.
@@ -99,25 +105,25 @@ pub struct CreateTenantRequest {
     pub test_phone_numbers: Option<std::collections::HashMap<String, String>>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub mfa_config: Option<serde_json::Value>,
+    pub mfa_config: Option<MultiFactorConfig>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub recaptcha_config: Option<serde_json::Value>,
+    pub recaptcha_config: Option<RecaptchaConfig>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub sms_region_config: Option<serde_json::Value>,
+    pub sms_region_config: Option<SmsRegionConfig>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub monitoring: Option<serde_json::Value>,
+    pub monitoring: Option<MonitoringConfig>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub password_policy_config: Option<serde_json::Value>,
+    pub password_policy_config: Option<PasswordPolicyConfig>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub email_privacy_config: Option<serde_json::Value>,
+    pub email_privacy_config: Option<EmailPrivacyConfig>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub client: Option<serde_json::Value>,
+    pub client: Option<ClientPermissionConfig>,
 }
 
 /// Request to update a tenant.
@@ -149,25 +155,76 @@ pub struct UpdateTenantRequest {
     pub test_phone_numbers: Option<std::collections::HashMap<String, String>>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub mfa_config: Option<serde_json::Value>,
+    pub mfa_config: Option<MultiFactorConfig>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub recaptcha_config: Option<serde_json::Value>,
+    pub recaptcha_config: Option<RecaptchaConfig>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub sms_region_config: Option<serde_json::Value>,
+    pub sms_region_config: Option<SmsRegionConfig>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub monitoring: Option<serde_json::Value>,
+    pub monitoring: Option<MonitoringConfig>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub password_policy_config: Option<serde_json::Value>,
+    pub password_policy_config: Option<PasswordPolicyConfig>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub email_privacy_config: Option<serde_json::Value>,
+    pub email_privacy_config: Option<EmailPrivacyConfig>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub client: Option<serde_json::Value>,
+    pub client: Option<ClientPermissionConfig>,
+}
+
+impl UpdateTenantRequest {
+    /// Builds a request that changes `current` into `desired`.
+    ///
+    /// Only fields that are set in `desired` and differ from `current` are included; fields
+    /// that are `None` in `desired` are left unchanged. Output-only fields, such as
+    /// `RecaptchaConfig::recaptcha_keys`, are ignored.
+    pub fn diff(current: &Tenant, desired: &Tenant) -> Self {
+        let recaptcha = |tenant: &Tenant| {
+            tenant.recaptcha_config.as_ref().map(RecaptchaConfig::without_output_only)
+        };
+        let password_policy = |tenant: &Tenant| {
+            tenant
+                .password_policy_config
+                .as_ref()
+                .map(PasswordPolicyConfig::without_output_only)
+        };
+        Self {
+            display_name: changed(&current.display_name, &desired.display_name),
+            allow_password_signup: changed(&current.allow_password_signup, &desired.allow_password_signup),
+            enable_email_link_signin: changed(&current.enable_email_link_signin, &desired.enable_email_link_signin),
+            disable_auth: changed(&current.disable_auth, &desired.disable_auth),
+            enable_anonymous_user: changed(&current.enable_anonymous_user, &desired.enable_anonymous_user),
+            test_phone_numbers: changed(&current.test_phone_numbers, &desired.test_phone_numbers),
+            mfa_config: changed(&current.mfa_config, &desired.mfa_config),
+            recaptcha_config: changed(&recaptcha(current), &recaptcha(desired)),
+            sms_region_config: changed(&current.sms_region_config, &desired.sms_region_config),
+            monitoring: changed(&current.monitoring, &desired.monitoring),
+            password_policy_config: changed(&password_policy(current), &password_policy(desired)),
+            email_privacy_config: changed(&current.email_privacy_config, &desired.email_privacy_config),
+            client: changed(&current.client, &desired.client),
+        }
+    }
+
+    /// Returns `true` if the request changes nothing.
+    pub fn is_empty(&self) -> bool {
+        self.display_name.is_none()
+            && self.allow_password_signup.is_none()
+            && self.enable_email_link_signin.is_none()
+            && self.disable_auth.is_none()
+            && self.enable_anonymous_user.is_none()
+            && self.test_phone_numbers.is_none()
+            && self.mfa_config.is_none()
+            && self.recaptcha_config.is_none()
+            && self.sms_region_config.is_none()
+            && self.monitoring.is_none()
+            && self.password_policy_config.is_none()
+            && self.email_privacy_config.is_none()
+            && self.client.is_none()
+    }
 }
 
 /// Response from listing tenants.
@@ -180,6 +237,19 @@ pub struct ListTenantsResponse {
     pub next_page_token: Option<String>,
 }
 
+fn validate_configs(
+    mfa_config: Option<&MultiFactorConfig>,
+    password_policy_config: Option<&PasswordPolicyConfig>,
+) -> Result<(), AuthError> {
+    if let Some(mfa_config) = mfa_config {
+        mfa_config.validate()?;
+    }
+    if let Some(password_policy_config) = password_policy_config {
+        password_policy_config.validate()?;
+    }
+    Ok(())
+}
+
 /// Manages tenants in a multi-tenant project.
 #[derive(Clone)]
 pub struct TenantAwareness {
@@ -190,14 +260,11 @@ pub struct TenantAwareness {
 
 impl TenantAwareness {
     pub(crate) fn new(middleware: AuthMiddleware) -> Self {
-        let retry_policy = ExponentialBackoff::builder().build_with_max_retries(3);
-        let client = ClientBuilder::new(Client::new())
-            .with(RetryTransientMiddleware::new_with_policy(retry_policy))
-            .with(middleware.clone())
-            .build();
+        let (origin, middleware) = identity_toolkit_endpoint(middleware);
+        let client = build_client(&middleware, "auth");
 
-        let project_id = middleware.key.project_id.clone().unwrap_or_default();
-        let base_url = format!("{}/projects/{}", IDENTITY_TOOLKIT_URL, project_id);
+        let project_id = middleware.project_id().to_string();
+        let base_url = format!("{}/v2/projects/{}", origin, project_id);
 
         Self {
             client,
@@ -212,24 +279,28 @@ impl TenantAwareness {
         FirebaseAuth::new(middleware)
     }
 
+    /// Returns a `ProjectConfig` that manages the OIDC and SAML providers of the specified
+    /// tenant.
+    pub fn project_config_for_tenant(&self, tenant_id: &str) -> ProjectConfig {
+        let middleware = self.middleware.with_tenant(tenant_id);
+        ProjectConfig::new(middleware)
+    }
+
     /// Creates a new tenant.
     pub async fn create_tenant(&self, request: CreateTenantRequest) -> Result<Tenant, AuthError> {
+        validate_configs(request.mfa_config.as_ref(), request.password_policy_config.as_ref())?;
         let url = format!("{}/tenants", self.base_url);
 
         let response = self
             .client
             .post(&url)
+            .with_extension(Operation("create_tenant"))
             .json(&request)
             .send()
             .await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            return Err(AuthError::ApiError(format!(
-                "Create tenant failed {}: {}",
-                status, text
-            )));
+            return Err(FirebaseError::from_response(response, "Create tenant failed").await.into());
         }
 
         let tenant: Tenant = response.json().await?;
@@ -240,15 +311,10 @@ impl TenantAwareness {
     pub async fn get_tenant(&self, tenant_id: &str) -> Result<Tenant, AuthError> {
         let url = format!("{}/tenants/{}", self.base_url, tenant_id);
 
-        let response = self.client.get(&url).send().await?;
+        let response = self.client.get(&url).with_extension(Operation("get_tenant")).send().await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            return Err(AuthError::ApiError(format!(
-                "Get tenant failed {}: {}",
-                status, text
-            )));
+            return Err(FirebaseError::from_response(response, "Get tenant failed").await.into());
         }
 
         let tenant: Tenant = response.json().await?;
@@ -261,6 +327,8 @@ impl TenantAwareness {
         tenant_id: &str,
         request: UpdateTenantRequest,
     ) -> Result<Tenant, AuthError> {
+        validate_configs(request.mfa_config.as_ref(), request.password_policy_config.as_ref())?;
+
         let url = format!("{}/tenants/{}", self.base_url, tenant_id);
 
         let mut mask_parts = Vec::new();
@@ -280,42 +348,46 @@ impl TenantAwareness {
 
         let update_mask = mask_parts.join(",");
 
-        let mut url_obj = Url::parse(&url).map_err(|e| AuthError::ApiError(e.to_string()))?;
+        let mut url_obj = Url::parse(&url)?;
         url_obj.query_pairs_mut().append_pair("updateMask", &update_mask);
 
         let response = self
             .client
             .patch(url_obj)
+            .with_extension(Operation("update_tenant"))
             .json(&request)
             .send()
             .await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            return Err(AuthError::ApiError(format!(
-                "Update tenant failed {}: {}",
-                status, text
-            )));
+            return Err(FirebaseError::from_response(response, "Update tenant failed").await.into());
         }
 
         let tenant: Tenant = response.json().await?;
         Ok(tenant)
     }
 
+    /// Updates a tenant to match `desired`, sending only the fields that changed.
+    ///
+    /// Fields that are `None` in `desired` are left unchanged. No update is sent if nothing
+    /// changed; the current tenant is returned instead.
+    pub async fn sync_tenant(&self, tenant_id: &str, desired: &Tenant) -> Result<Tenant, AuthError> {
+        let current = self.get_tenant(tenant_id).await?;
+        let request = UpdateTenantRequest::diff(&current, desired);
+        if request.is_empty() {
+            return Ok(current);
+        }
+        self.update_tenant(tenant_id, request).await
+    }
+
     /// Deletes a tenant.
     pub async fn delete_tenant(&self, tenant_id: &str) -> Result<(), AuthError> {
         let url = format!("{}/tenants/{}", self.base_url, tenant_id);
 
-        let response = self.client.delete(&url).send().await?;
+        let response = self.client.delete(&url).with_extension(Operation("delete_tenant")).send().await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            return Err(AuthError::ApiError(format!(
-                "Delete tenant failed {}: {}",
-                status, text
-            )));
+            return Err(FirebaseError::from_response(response, "Delete tenant failed").await.into());
         }
 
         Ok(())
@@ -328,7 +400,7 @@ impl TenantAwareness {
         page_token: Option<&str>,
     ) -> Result<ListTenantsResponse, AuthError> {
         let url = format!("{}/tenants", self.base_url);
-        let mut url_obj = Url::parse(&url).map_err(|e| AuthError::ApiError(e.to_string()))?;
+        let mut url_obj = Url::parse(&url)?;
 
         {
             let mut query_pairs = url_obj.query_pairs_mut();
@@ -340,18 +412,30 @@ impl TenantAwareness {
             }
         }
 
-        let response = self.client.get(url_obj).send().await?;
+        let response = self.client.get(url_obj).with_extension(Operation("list_tenants")).send().await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            return Err(AuthError::ApiError(format!(
-                "List tenants failed {}: {}",
-                status, text
-            )));
+            return Err(FirebaseError::from_response(response, "List tenants failed").await.into());
         }
 
         let result: ListTenantsResponse = response.json().await?;
         Ok(result)
     }
+
+    /// Lists all tenants as a stream, following page tokens until the last page.
+    pub fn list_all_tenants(
+        &self,
+        options: ListOptions,
+    ) -> impl Stream<Item = Result<Tenant, AuthError>> + Send + 'static {
+        let tenants = self.clone();
+        paginate(options, move |page_token| {
+            let tenants = tenants.clone();
+            async move {
+                let page = tenants
+                    .list_tenants(options.page_size, page_token.as_deref())
+                    .await?;
+                Ok((page.tenants.unwrap_or_default(), page.next_page_token))
+            }
+        })
+    }
 }
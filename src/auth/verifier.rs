@@ -1,8 +1,13 @@
-use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+use base64::alphabet;
+use base64::engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig};
+use base64::Engine;
+use jsonwebtoken::{decode, decode_header, Algorithm, Validation};
 use serde::{Deserialize, Serialize};
-use crate::auth::keys::{PublicKeyManager, KeyFetchError};
+use crate::auth::keys::{KeyFetchError, KeySource, PublicKeyManager, SESSION_COOKIE_CERTS_URL};
+use crate::core::clock::{Clock, SystemClock};
+use std::sync::Arc;
+use std::time::{Duration, UNIX_EPOCH};
 use thiserror::Error;
-use std::time::{SystemTime, UNIX_EPOCH};
 
 #[derive(Error, Debug)]
 pub enum TokenVerificationError {
@@ -14,9 +19,23 @@ pub enum TokenVerificationError {
     InvalidToken(String),
     #[error("Token expired")]
     Expired,
+    /// The token was issued before the user's refresh tokens were revoked.
+    #[error("Token has been revoked")]
+    Revoked,
+    /// The user the token was issued for is disabled.
+    #[error("User is disabled")]
+    UserDisabled,
+    /// The token was issued for a different tenant than the one the client is scoped to.
+    #[error("Token tenant {actual:?} does not match expected tenant {expected}")]
+    TenantIdMismatch {
+        /// The tenant the client is scoped to.
+        expected: String,
+        /// The tenant the token was issued for.
+        actual: Option<String>,
+    },
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Default)]
 pub struct FirebaseTokenClaims {
     pub aud: String,
     pub iss: String,
@@ -25,63 +44,247 @@ pub struct FirebaseTokenClaims {
     pub iat: usize,
     pub auth_time: usize,
     pub user_id: String,
+    /// The user's email address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    /// Whether the user's email address is verified.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email_verified: Option<bool>,
+    /// The user's phone number.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phone_number: Option<String>,
+    /// The user's photo URL.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub picture: Option<String>,
+    /// The user's display name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Firebase specific sign-in information.
+    #[serde(default)]
+    pub firebase: FirebaseInfo,
+    /// All other claims, including custom claims.
     #[serde(flatten)]
     pub claims: serde_json::Map<String, serde_json::Value>,
 }
 
+/// The `firebase` claim of an ID token or session cookie.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct FirebaseInfo {
+    /// The provider used to sign in (e.g., `password`, `google.com`, `custom`).
+    #[serde(default)]
+    pub sign_in_provider: String,
+    /// The second factor used to sign in (e.g., `phone`, `totp`), if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sign_in_second_factor: Option<String>,
+    /// The enrollment ID of the second factor used to sign in, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub second_factor_identifier: Option<String>,
+    /// The tenant the user belongs to, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tenant: Option<String>,
+    /// The user's identities, keyed by provider (e.g., `email`, `google.com`).
+    #[serde(default)]
+    pub identities: std::collections::HashMap<String, Vec<serde_json::Value>>,
+}
+
+/// The clock skew tolerated by default when checking `exp`, `iat` and `auth_time`.
+pub const DEFAULT_LEEWAY: Duration = Duration::from_secs(60);
+
+/// Verifies Firebase ID tokens and session cookies.
+///
+/// Keys are fetched from Google's x509 endpoints and time is read from the system clock unless
+/// configured otherwise, e.g. for air-gapped or deterministic tests:
+///
+/// ```rust,no_run
+/// use firebase_admin_sdk::auth::keys::StaticKeySource;
+/// use firebase_admin_sdk::auth::verifier::IdTokenVerifier;
+/// use firebase_admin_sdk::core::clock::FixedClock;
+/// use std::collections::HashMap;
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// let keys = HashMap::from([("key-1".to_string(), "-----BEGIN PUBLIC KEY-----...".to_string())]);
+/// let verifier = IdTokenVerifier::new("my-project".to_string())
+///     .with_key_source(StaticKeySource::from_pem_map(&keys).unwrap())
+///     .with_clock(FixedClock::new(UNIX_EPOCH + Duration::from_secs(1_700_000_000)))
+///     .with_leeway(Duration::ZERO);
+/// ```
 pub struct IdTokenVerifier {
     project_id: String,
-    key_manager: PublicKeyManager,
+    key_source: Arc<dyn KeySource>,
+    session_key_source: Arc<dyn KeySource>,
+    clock: Arc<dyn Clock>,
+    leeway: Duration,
+    emulator: bool,
 }
 
 impl IdTokenVerifier {
     pub fn new(project_id: String) -> Self {
         Self {
             project_id,
-            key_manager: PublicKeyManager::new(),
+            key_source: Arc::new(PublicKeyManager::new()),
+            session_key_source: Arc::new(PublicKeyManager::with_url(SESSION_COOKIE_CERTS_URL)),
+            clock: Arc::new(SystemClock),
+            leeway: DEFAULT_LEEWAY,
+            emulator: false,
         }
     }
 
+    /// Accepts the unsigned (`alg: none`) tokens issued by the Auth emulator.
+    ///
+    /// Signatures are not checked, but issuer, audience, subject and time based claims are.
+    /// Enabled automatically for clients created while the Auth emulator is configured; never
+    /// enable this in production.
+    pub fn with_emulator(mut self, emulator: bool) -> Self {
+        self.emulator = emulator;
+        self
+    }
+
+    /// Sets the source of the keys used to verify ID tokens.
+    pub fn with_key_source(mut self, key_source: impl KeySource + 'static) -> Self {
+        self.key_source = Arc::new(key_source);
+        self
+    }
+
+    /// Sets the source of the keys used to verify session cookies.
+    pub fn with_session_cookie_key_source(mut self, key_source: impl KeySource + 'static) -> Self {
+        self.session_key_source = Arc::new(key_source);
+        self
+    }
+
+    /// Sets the clock used to check `exp`, `iat` and `auth_time`.
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Gets the clock used to check `exp`, `iat` and `auth_time`.
+    pub(crate) fn clock(&self) -> &dyn Clock {
+        self.clock.as_ref()
+    }
+
+    /// Sets the tolerated clock skew. Defaults to [`DEFAULT_LEEWAY`].
+    pub fn with_leeway(mut self, leeway: Duration) -> Self {
+        self.leeway = leeway;
+        self
+    }
+
     /// Verifies a Firebase ID token.
     pub async fn verify_id_token(&self, token: &str) -> Result<FirebaseTokenClaims, TokenVerificationError> {
-        self.verify_token_with_issuer(token, &format!("https://securetoken.google.com/{}", self.project_id)).await
+        let issuer = format!("https://securetoken.google.com/{}", self.project_id);
+        self.verify_token_with_issuer(token, &issuer, self.key_source.as_ref()).await
     }
 
     /// Verifies a Firebase Session Cookie.
     pub async fn verify_session_cookie(&self, token: &str) -> Result<FirebaseTokenClaims, TokenVerificationError> {
-        self.verify_token_with_issuer(token, &format!("https://session.firebase.google.com/{}", self.project_id)).await
+        let issuer = format!("https://session.firebase.google.com/{}", self.project_id);
+        self.verify_token_with_issuer(token, &issuer, self.session_key_source.as_ref()).await
     }
 
-    async fn verify_token_with_issuer(&self, token: &str, issuer: &str) -> Result<FirebaseTokenClaims, TokenVerificationError> {
-        // 1. Decode header to get kid
-        let header = decode_header(token)?;
-        let kid = header.kid.ok_or_else(|| TokenVerificationError::InvalidToken("Missing kid in header".to_string()))?;
-
-        // 2. Get public key
-        let public_key_pem = self.key_manager.get_key(&kid).await?;
-        let key = DecodingKey::from_rsa_pem(public_key_pem.as_bytes())?;
-
-        // 3. Configure validation
-        let mut validation = Validation::new(Algorithm::RS256);
-        validation.set_audience(&[&self.project_id]);
-        validation.set_issuer(&[issuer]);
+    async fn verify_token_with_issuer(
+        &self,
+        token: &str,
+        issuer: &str,
+        key_source: &dyn KeySource,
+    ) -> Result<FirebaseTokenClaims, TokenVerificationError> {
+        let claims = if self.emulator {
+            decode_unsigned(token)?
+        } else {
+            decode_signed(token, key_source).await?
+        };
 
-        // 4. Verify
-        let token_data = decode::<FirebaseTokenClaims>(token, &key, &validation)?;
-        let claims = token_data.claims;
-
-        // 5. Additional validations (sub not empty, auth_time < now)
+        // Claim validations (audience, issuer, sub not empty, time based claims)
+        if claims.aud != self.project_id {
+            return Err(TokenVerificationError::InvalidToken(format!(
+                "Unexpected audience {}, expected {}",
+                claims.aud, self.project_id
+            )));
+        }
+        if claims.iss != issuer {
+            return Err(TokenVerificationError::InvalidToken(format!(
+                "Unexpected issuer {}, expected {}",
+                claims.iss, issuer
+            )));
+        }
         if claims.sub.is_empty() {
             return Err(TokenVerificationError::InvalidToken("Subject (sub) claim must not be empty".to_string()));
         }
 
-        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() as usize;
-        // Allowing some clock skew? jsonwebtoken handles exp/iat with leeway.
-        // auth_time validation usually not strictly enforced by jsonwebtoken default.
-        if claims.auth_time > now + 300 { // 5 minutes future skew tolerance
-             return Err(TokenVerificationError::InvalidToken("Auth time is in the future".to_string()));
+        let now = self
+            .clock
+            .now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as usize;
+        let leeway = self.leeway.as_secs() as usize;
+        if now > claims.exp.saturating_add(leeway) {
+            return Err(TokenVerificationError::Expired);
+        }
+        if claims.iat > now.saturating_add(leeway) {
+            return Err(TokenVerificationError::InvalidToken("Token issued in the future".to_string()));
+        }
+        if claims.auth_time > now.saturating_add(leeway) {
+            return Err(TokenVerificationError::InvalidToken("Auth time is in the future".to_string()));
         }
 
         Ok(claims)
     }
 }
+
+/// Verifies the RS256 signature of `token` and decodes its claims.
+async fn decode_signed(
+    token: &str,
+    key_source: &dyn KeySource,
+) -> Result<FirebaseTokenClaims, TokenVerificationError> {
+    // 1. Decode header to get kid
+    let header = decode_header(token)?;
+    if header.alg != Algorithm::RS256 {
+        return Err(TokenVerificationError::InvalidToken(format!(
+            "Unexpected algorithm {:?}, expected RS256",
+            header.alg
+        )));
+    }
+    let kid = header.kid.ok_or_else(|| TokenVerificationError::InvalidToken("Missing kid in header".to_string()))?;
+
+    // 2. Get public key
+    let key = key_source.get_key(&kid).await?;
+
+    // 3. Verify the signature; claims are validated by the caller against its clock
+    let mut validation = Validation::new(Algorithm::RS256);
+    validation.validate_aud = false;
+    validation.validate_exp = false;
+    validation.validate_nbf = false;
+    Ok(decode::<FirebaseTokenClaims>(token, &key, &validation)?.claims)
+}
+
+/// The header fields of an emulator token that are checked.
+#[derive(Deserialize)]
+struct UnsignedHeader {
+    alg: String,
+}
+
+/// Decodes the claims of an unsigned (`alg: none`) emulator token.
+///
+/// Tokens with any other algorithm are rejected, since their signature is not checked here.
+fn decode_unsigned(token: &str) -> Result<FirebaseTokenClaims, TokenVerificationError> {
+    let engine = GeneralPurpose::new(
+        &alphabet::URL_SAFE,
+        GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+    );
+    let malformed = || TokenVerificationError::InvalidToken("Malformed token".to_string());
+
+    let parts: Vec<&str> = token.split('.').collect();
+    let [header, payload, _] = parts.as_slice() else {
+        return Err(malformed());
+    };
+    let header = engine.decode(header).map_err(|_| malformed())?;
+    let header: UnsignedHeader = serde_json::from_slice(&header).map_err(|_| malformed())?;
+    if header.alg != "none" {
+        return Err(TokenVerificationError::InvalidToken(format!(
+            "Unexpected algorithm {}, expected none for emulator tokens",
+            header.alg
+        )));
+    }
+    let payload = engine.decode(payload).map_err(|_| malformed())?;
+    serde_json::from_slice(&payload)
+        .map_err(|e| TokenVerificationError::InvalidToken(format!("Invalid claims: {}", e)))
+}
@@ -0,0 +1,250 @@
+//! Blocking Firebase Authentication client.
+
+use crate::auth::models::{
+    ActionCodeSettings, CreateUserRequest, DeleteUsersResult, GetUsersResult, ImportUsersRequest,
+    ListUsersResponse, UpdateUserRequest, UserIdentifier, UserImportResult, UserRecord,
+};
+use crate::auth::pagination::ListOptions;
+use crate::auth::verifier::{FirebaseTokenClaims, IdTokenVerifier};
+use crate::auth::AuthError;
+use futures::StreamExt;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::runtime::Runtime;
+
+/// A blocking counterpart of [`crate::auth::FirebaseAuth`].
+pub struct FirebaseAuth {
+    inner: crate::auth::FirebaseAuth,
+    runtime: Arc<Runtime>,
+}
+
+impl FirebaseAuth {
+    pub(crate) fn new(inner: crate::auth::FirebaseAuth, runtime: Arc<Runtime>) -> Self {
+        Self { inner, runtime }
+    }
+
+    /// Same as [`crate::auth::FirebaseAuth::with_token_verifier`].
+    pub fn with_token_verifier(self, verifier: IdTokenVerifier) -> Self {
+        Self {
+            inner: self.inner.with_token_verifier(verifier),
+            ..self
+        }
+    }
+
+    /// Blocking version of [`crate::auth::FirebaseAuth::verify_id_token`].
+    pub fn verify_id_token(
+        &self,
+        token: &str,
+        check_revoked: bool,
+    ) -> Result<FirebaseTokenClaims, AuthError> {
+        self.runtime
+            .block_on(self.inner.verify_id_token(token, check_revoked))
+    }
+
+    /// Blocking version of [`crate::auth::FirebaseAuth::create_session_cookie`].
+    pub fn create_session_cookie(
+        &self,
+        id_token: &str,
+        valid_duration: Duration,
+    ) -> Result<String, AuthError> {
+        self.runtime
+            .block_on(self.inner.create_session_cookie(id_token, valid_duration))
+    }
+
+    /// Blocking version of [`crate::auth::FirebaseAuth::verify_session_cookie`].
+    pub fn verify_session_cookie(
+        &self,
+        session_cookie: &str,
+        check_revoked: bool,
+    ) -> Result<FirebaseTokenClaims, AuthError> {
+        self.runtime
+            .block_on(self.inner.verify_session_cookie(session_cookie, check_revoked))
+    }
+
+    /// Blocking version of [`crate::auth::FirebaseAuth::revoke_refresh_tokens`].
+    pub fn revoke_refresh_tokens(&self, uid: &str) -> Result<(), AuthError> {
+        self.runtime.block_on(self.inner.revoke_refresh_tokens(uid))
+    }
+
+    /// Blocking version of [`crate::auth::FirebaseAuth::create_custom_token`].
+    pub fn create_custom_token(
+        &self,
+        uid: &str,
+        custom_claims: Option<serde_json::Map<String, serde_json::Value>>,
+    ) -> Result<String, AuthError> {
+        self.runtime
+            .block_on(self.inner.create_custom_token(uid, custom_claims))
+    }
+
+    /// Blocking version of [`crate::auth::FirebaseAuth::generate_password_reset_link`].
+    pub fn generate_password_reset_link(
+        &self,
+        email: &str,
+        settings: Option<ActionCodeSettings>,
+    ) -> Result<String, AuthError> {
+        self.runtime
+            .block_on(self.inner.generate_password_reset_link(email, settings))
+    }
+
+    /// Blocking version of [`crate::auth::FirebaseAuth::generate_email_verification_link`].
+    pub fn generate_email_verification_link(
+        &self,
+        email: &str,
+        settings: Option<ActionCodeSettings>,
+    ) -> Result<String, AuthError> {
+        self.runtime
+            .block_on(self.inner.generate_email_verification_link(email, settings))
+    }
+
+    /// Blocking version of [`crate::auth::FirebaseAuth::generate_sign_in_with_email_link`].
+    pub fn generate_sign_in_with_email_link(
+        &self,
+        email: &str,
+        settings: Option<ActionCodeSettings>,
+    ) -> Result<String, AuthError> {
+        self.runtime
+            .block_on(self.inner.generate_sign_in_with_email_link(email, settings))
+    }
+
+    /// Blocking version of [`crate::auth::FirebaseAuth::generate_verify_and_change_email_link`].
+    pub fn generate_verify_and_change_email_link(
+        &self,
+        email: &str,
+        new_email: &str,
+        settings: Option<ActionCodeSettings>,
+    ) -> Result<String, AuthError> {
+        self.runtime.block_on(
+            self.inner
+                .generate_verify_and_change_email_link(email, new_email, settings),
+        )
+    }
+
+    /// Blocking version of [`crate::auth::FirebaseAuth::send_password_reset_email`].
+    pub fn send_password_reset_email(
+        &self,
+        email: &str,
+        settings: Option<ActionCodeSettings>,
+    ) -> Result<(), AuthError> {
+        self.runtime
+            .block_on(self.inner.send_password_reset_email(email, settings))
+    }
+
+    /// Blocking version of [`crate::auth::FirebaseAuth::send_verification_email`].
+    pub fn send_verification_email(
+        &self,
+        email: &str,
+        settings: Option<ActionCodeSettings>,
+    ) -> Result<(), AuthError> {
+        self.runtime
+            .block_on(self.inner.send_verification_email(email, settings))
+    }
+
+    /// Blocking version of [`crate::auth::FirebaseAuth::send_sign_in_email`].
+    pub fn send_sign_in_email(
+        &self,
+        email: &str,
+        settings: Option<ActionCodeSettings>,
+    ) -> Result<(), AuthError> {
+        self.runtime
+            .block_on(self.inner.send_sign_in_email(email, settings))
+    }
+
+    /// Blocking version of [`crate::auth::FirebaseAuth::send_verify_and_change_email`].
+    pub fn send_verify_and_change_email(
+        &self,
+        email: &str,
+        new_email: &str,
+        settings: Option<ActionCodeSettings>,
+    ) -> Result<(), AuthError> {
+        self.runtime
+            .block_on(self.inner.send_verify_and_change_email(email, new_email, settings))
+    }
+
+    /// Blocking version of [`crate::auth::FirebaseAuth::import_users`].
+    pub fn import_users(
+        &self,
+        request: ImportUsersRequest,
+    ) -> Result<UserImportResult, AuthError> {
+        self.runtime.block_on(self.inner.import_users(request))
+    }
+
+    /// Blocking version of [`crate::auth::FirebaseAuth::create_user`].
+    pub fn create_user(&self, request: CreateUserRequest) -> Result<UserRecord, AuthError> {
+        self.runtime.block_on(self.inner.create_user(request))
+    }
+
+    /// Blocking version of [`crate::auth::FirebaseAuth::update_user`].
+    pub fn update_user(&self, request: UpdateUserRequest) -> Result<UserRecord, AuthError> {
+        self.runtime.block_on(self.inner.update_user(request))
+    }
+
+    /// Blocking version of [`crate::auth::FirebaseAuth::set_custom_user_claims`].
+    pub fn set_custom_user_claims(
+        &self,
+        uid: &str,
+        claims: Option<impl serde::Serialize>,
+    ) -> Result<(), AuthError> {
+        self.runtime
+            .block_on(self.inner.set_custom_user_claims(uid, claims))
+    }
+
+    /// Blocking version of [`crate::auth::FirebaseAuth::delete_user`].
+    pub fn delete_user(&self, uid: &str) -> Result<(), AuthError> {
+        self.runtime.block_on(self.inner.delete_user(uid))
+    }
+
+    /// Blocking version of [`crate::auth::FirebaseAuth::delete_users`].
+    pub fn delete_users(&self, uids: &[&str], force: bool) -> Result<DeleteUsersResult, AuthError> {
+        self.runtime.block_on(self.inner.delete_users(uids, force))
+    }
+
+    /// Blocking version of [`crate::auth::FirebaseAuth::get_user`].
+    pub fn get_user(&self, uid: &str) -> Result<UserRecord, AuthError> {
+        self.runtime.block_on(self.inner.get_user(uid))
+    }
+
+    /// Blocking version of [`crate::auth::FirebaseAuth::get_user_by_email`].
+    pub fn get_user_by_email(&self, email: &str) -> Result<UserRecord, AuthError> {
+        self.runtime.block_on(self.inner.get_user_by_email(email))
+    }
+
+    /// Blocking version of [`crate::auth::FirebaseAuth::get_user_by_phone_number`].
+    pub fn get_user_by_phone_number(&self, phone: &str) -> Result<UserRecord, AuthError> {
+        self.runtime
+            .block_on(self.inner.get_user_by_phone_number(phone))
+    }
+
+    /// Blocking version of [`crate::auth::FirebaseAuth::get_user_by_provider_uid`].
+    pub fn get_user_by_provider_uid(
+        &self,
+        provider_id: &str,
+        uid: &str,
+    ) -> Result<UserRecord, AuthError> {
+        self.runtime
+            .block_on(self.inner.get_user_by_provider_uid(provider_id, uid))
+    }
+
+    /// Blocking version of [`crate::auth::FirebaseAuth::get_users`].
+    pub fn get_users(&self, identifiers: &[UserIdentifier]) -> Result<GetUsersResult, AuthError> {
+        self.runtime.block_on(self.inner.get_users(identifiers))
+    }
+
+    /// Blocking version of [`crate::auth::FirebaseAuth::list_users`].
+    pub fn list_users(
+        &self,
+        max_results: u32,
+        page_token: Option<&str>,
+    ) -> Result<ListUsersResponse, AuthError> {
+        self.runtime
+            .block_on(self.inner.list_users(max_results, page_token))
+    }
+
+    /// Blocking version of [`crate::auth::FirebaseAuth::list_all_users`], returning an iterator.
+    pub fn list_all_users(
+        &self,
+        options: ListOptions,
+    ) -> impl Iterator<Item = Result<UserRecord, AuthError>> + '_ {
+        let mut users = Box::pin(self.inner.list_all_users(options));
+        std::iter::from_fn(move || self.runtime.block_on(users.next()))
+    }
+}
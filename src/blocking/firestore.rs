@@ -0,0 +1,118 @@
+//! Blocking Cloud Firestore client.
+
+use crate::firestore::models::ListDocumentsResponse;
+use crate::firestore::snapshot::{DocumentSnapshot, WriteResult};
+use crate::firestore::FirestoreError;
+use serde::Serialize;
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+/// A blocking counterpart of [`crate::firestore::FirebaseFirestore`].
+pub struct FirebaseFirestore {
+    inner: crate::firestore::FirebaseFirestore,
+    runtime: Arc<Runtime>,
+}
+
+impl FirebaseFirestore {
+    pub(crate) fn new(inner: crate::firestore::FirebaseFirestore, runtime: Arc<Runtime>) -> Self {
+        Self { inner, runtime }
+    }
+
+    /// Gets a `CollectionReference` instance that refers to the collection at the specified path.
+    pub fn collection(&self, collection_id: &str) -> CollectionReference<'_> {
+        CollectionReference {
+            inner: self.inner.collection(collection_id),
+            runtime: &self.runtime,
+        }
+    }
+
+    /// Gets a `DocumentReference` instance that refers to the document at the specified path.
+    pub fn doc(&self, document_path: &str) -> DocumentReference<'_> {
+        DocumentReference {
+            inner: self.inner.doc(document_path),
+            runtime: &self.runtime,
+        }
+    }
+
+    /// Blocking version of [`crate::firestore::FirebaseFirestore::list_collections`].
+    pub fn list_collections(&self) -> Result<Vec<CollectionReference<'_>>, FirestoreError> {
+        let collections = self.runtime.block_on(self.inner.list_collections())?;
+        Ok(collections
+            .into_iter()
+            .map(|inner| CollectionReference {
+                inner,
+                runtime: &self.runtime,
+            })
+            .collect())
+    }
+}
+
+/// A blocking counterpart of [`crate::firestore::reference::DocumentReference`].
+pub struct DocumentReference<'a> {
+    inner: crate::firestore::reference::DocumentReference<'a>,
+    runtime: &'a Runtime,
+}
+
+impl<'a> DocumentReference<'a> {
+    /// Blocking version of [`crate::firestore::reference::DocumentReference::get`].
+    pub fn get(&self) -> Result<DocumentSnapshot<'a>, FirestoreError> {
+        self.runtime.block_on(self.inner.get())
+    }
+
+    /// Gets a `CollectionReference` for the subcollection with the specified ID.
+    pub fn collection(&self, collection_id: &str) -> CollectionReference<'a> {
+        CollectionReference {
+            inner: self.inner.collection(collection_id),
+            runtime: self.runtime,
+        }
+    }
+
+    /// Blocking version of [`crate::firestore::reference::DocumentReference::set`].
+    pub fn set<T: Serialize>(&self, value: &T) -> Result<WriteResult, FirestoreError> {
+        self.runtime.block_on(self.inner.set(value))
+    }
+
+    /// Blocking version of [`crate::firestore::reference::DocumentReference::update`].
+    pub fn update<T: Serialize>(
+        &self,
+        value: &T,
+        update_mask: Option<Vec<String>>,
+    ) -> Result<WriteResult, FirestoreError> {
+        self.runtime.block_on(self.inner.update(value, update_mask))
+    }
+
+    /// Blocking version of [`crate::firestore::reference::DocumentReference::delete`].
+    pub fn delete(&self) -> Result<WriteResult, FirestoreError> {
+        self.runtime.block_on(self.inner.delete())
+    }
+}
+
+/// A blocking counterpart of [`crate::firestore::reference::CollectionReference`].
+pub struct CollectionReference<'a> {
+    inner: crate::firestore::reference::CollectionReference<'a>,
+    runtime: &'a Runtime,
+}
+
+impl<'a> CollectionReference<'a> {
+    /// Gets a `DocumentReference` for the document within the collection with the specified ID.
+    pub fn doc(&self, document_id: &str) -> DocumentReference<'a> {
+        DocumentReference {
+            inner: self.inner.doc(document_id),
+            runtime: self.runtime,
+        }
+    }
+
+    /// Blocking version of [`crate::firestore::reference::CollectionReference::list_documents`].
+    pub fn list_documents(&self) -> Result<ListDocumentsResponse, FirestoreError> {
+        self.runtime.block_on(self.inner.list_documents())
+    }
+
+    /// Blocking version of [`crate::firestore::reference::CollectionReference::add`].
+    pub fn add<T: Serialize>(&self, value: &T) -> Result<DocumentReference<'a>, FirestoreError> {
+        let inner = self.runtime.block_on(self.inner.add(value))?;
+        Ok(DocumentReference {
+            inner,
+            runtime: self.runtime,
+        })
+    }
+}
@@ -0,0 +1,62 @@
+//! Blocking Firebase Cloud Messaging client.
+
+use crate::messaging::models::{BatchResponse, Message, MulticastMessage, TopicManagementResponse};
+use crate::messaging::MessagingError;
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+/// A blocking counterpart of [`crate::messaging::FirebaseMessaging`].
+pub struct FirebaseMessaging {
+    inner: crate::messaging::FirebaseMessaging,
+    runtime: Arc<Runtime>,
+}
+
+impl FirebaseMessaging {
+    pub(crate) fn new(inner: crate::messaging::FirebaseMessaging, runtime: Arc<Runtime>) -> Self {
+        Self { inner, runtime }
+    }
+
+    /// Blocking version of [`crate::messaging::FirebaseMessaging::send`].
+    pub fn send(&self, message: &Message, dry_run: bool) -> Result<String, MessagingError> {
+        self.runtime.block_on(self.inner.send(message, dry_run))
+    }
+
+    /// Blocking version of [`crate::messaging::FirebaseMessaging::send_each`].
+    pub fn send_each(
+        &self,
+        messages: &[Message],
+        dry_run: bool,
+    ) -> Result<BatchResponse, MessagingError> {
+        self.runtime.block_on(self.inner.send_each(messages, dry_run))
+    }
+
+    /// Blocking version of [`crate::messaging::FirebaseMessaging::send_each_for_multicast`].
+    pub fn send_each_for_multicast(
+        &self,
+        message: &MulticastMessage,
+        dry_run: bool,
+    ) -> Result<BatchResponse, MessagingError> {
+        self.runtime
+            .block_on(self.inner.send_each_for_multicast(message, dry_run))
+    }
+
+    /// Blocking version of [`crate::messaging::FirebaseMessaging::subscribe_to_topic`].
+    pub fn subscribe_to_topic(
+        &self,
+        tokens: &[&str],
+        topic: &str,
+    ) -> Result<TopicManagementResponse, MessagingError> {
+        self.runtime
+            .block_on(self.inner.subscribe_to_topic(tokens, topic))
+    }
+
+    /// Blocking version of [`crate::messaging::FirebaseMessaging::unsubscribe_from_topic`].
+    pub fn unsubscribe_from_topic(
+        &self,
+        tokens: &[&str],
+        topic: &str,
+    ) -> Result<TopicManagementResponse, MessagingError> {
+        self.runtime
+            .block_on(self.inner.unsubscribe_from_topic(tokens, topic))
+    }
+}
@@ -0,0 +1,135 @@
+//! Blocking (synchronous) facade for the SDK.
+//!
+//! The types in this module mirror the async API but block the calling thread until the
+//! request completes, driving an internal Tokio runtime in the same way `reqwest::blocking`
+//! does. They are meant for CLI tools and other synchronous code.
+//!
+//! The blocking clients must not be used from within an async runtime; doing so panics.
+//! Use the async API instead.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use firebase_admin_sdk::blocking::FirebaseApp;
+//!
+//! let app = FirebaseApp::application_default().unwrap();
+//! let user = app.auth().get_user("some-uid").unwrap();
+//! println!("{:?}", user.email);
+//! ```
+
+#[cfg(feature = "auth")]
+pub mod auth;
+#[cfg(feature = "firestore")]
+pub mod firestore;
+#[cfg(feature = "messaging")]
+pub mod messaging;
+#[cfg(feature = "storage")]
+pub mod storage;
+
+use crate::builder::BuildError;
+use crate::core::credential::{AccessToken, Credential, CredentialError};
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+use yup_oauth2::ServiceAccountKey;
+
+/// A blocking counterpart of [`crate::FirebaseApp`].
+///
+/// Service clients created from this app share its runtime and HTTP connection pool.
+pub struct FirebaseApp {
+    inner: crate::FirebaseApp,
+    runtime: Arc<Runtime>,
+}
+
+impl FirebaseApp {
+    /// Creates a new `FirebaseApp` from a service account key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal Tokio runtime cannot be started.
+    pub fn new(service_account_key: ServiceAccountKey) -> Result<Self, BuildError> {
+        Ok(Self::from_app(crate::FirebaseApp::new(service_account_key)?))
+    }
+
+    /// Creates a new `FirebaseApp` backed by an arbitrary [`Credential`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal Tokio runtime cannot be started.
+    pub fn from_credential(credential: impl Credential + 'static) -> Result<Self, BuildError> {
+        Ok(Self::from_app(crate::FirebaseApp::from_credential(credential)?))
+    }
+
+    /// Creates a new `FirebaseApp` using Application Default Credentials.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal Tokio runtime cannot be started.
+    pub fn application_default() -> Result<Self, BuildError> {
+        Ok(Self::from_app(crate::FirebaseApp::application_default()?))
+    }
+
+    /// Wraps an async app, e.g. one configured with [`crate::FirebaseApp::builder`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal Tokio runtime cannot be started.
+    pub fn from_app(app: crate::FirebaseApp) -> Self {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("failed to start the Tokio runtime for the blocking client");
+
+        Self {
+            inner: app,
+            runtime: Arc::new(runtime),
+        }
+    }
+
+    /// Returns the underlying async app.
+    pub fn app(&self) -> &crate::FirebaseApp {
+        &self.inner
+    }
+
+    /// Returns a valid OAuth2 access token and its expiry.
+    pub fn access_token(&self) -> Result<AccessToken, CredentialError> {
+        self.runtime.block_on(self.inner.access_token())
+    }
+
+    /// Fetches an access token ahead of the first API call.
+    pub fn prewarm(&self) -> Result<(), CredentialError> {
+        self.runtime.block_on(self.inner.prewarm())
+    }
+
+    /// Returns a blocking client for Firebase Authentication.
+    #[cfg(feature = "auth")]
+    pub fn auth(&self) -> auth::FirebaseAuth {
+        auth::FirebaseAuth::new(self.inner.auth(), self.runtime.clone())
+    }
+
+    /// Returns a blocking client for Cloud Firestore.
+    #[cfg(feature = "firestore")]
+    pub fn firestore(&self) -> firestore::FirebaseFirestore {
+        firestore::FirebaseFirestore::new(self.inner.firestore(), self.runtime.clone())
+    }
+
+    /// Returns a blocking client for Firebase Cloud Messaging.
+    #[cfg(feature = "messaging")]
+    pub fn messaging(&self) -> messaging::FirebaseMessaging {
+        messaging::FirebaseMessaging::new(self.inner.messaging(), self.runtime.clone())
+    }
+
+    /// Returns a blocking client for Cloud Storage.
+    #[cfg(feature = "storage")]
+    pub fn storage(&self) -> storage::FirebaseStorage {
+        storage::FirebaseStorage::new(self.inner.storage(), self.runtime.clone())
+    }
+}
+
+impl From<crate::FirebaseApp> for FirebaseApp {
+    fn from(app: crate::FirebaseApp) -> Self {
+        Self::from_app(app)
+    }
+}
+
+#[cfg(all(test, any(feature = "auth", all(feature = "firestore", feature = "storage"))))]
+mod tests;
@@ -0,0 +1,95 @@
+//! Blocking Cloud Storage client.
+
+use crate::storage::file::{GetSignedUrlOptions, ObjectMetadata};
+use crate::storage::StorageError;
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+/// A blocking counterpart of [`crate::storage::FirebaseStorage`].
+pub struct FirebaseStorage {
+    inner: crate::storage::FirebaseStorage,
+    runtime: Arc<Runtime>,
+}
+
+impl FirebaseStorage {
+    pub(crate) fn new(inner: crate::storage::FirebaseStorage, runtime: Arc<Runtime>) -> Self {
+        Self { inner, runtime }
+    }
+
+    /// Gets a `Bucket` instance. See [`crate::storage::FirebaseStorage::bucket`].
+    pub fn bucket(&self, name: Option<&str>) -> Bucket {
+        Bucket {
+            inner: self.inner.bucket(name),
+            runtime: self.runtime.clone(),
+        }
+    }
+}
+
+/// A blocking counterpart of [`crate::storage::bucket::Bucket`].
+pub struct Bucket {
+    inner: crate::storage::bucket::Bucket,
+    runtime: Arc<Runtime>,
+}
+
+impl Bucket {
+    /// Returns the name of the bucket.
+    pub fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    /// Gets a `File` instance that refers to the file at the specified path.
+    pub fn file(&self, name: &str) -> File {
+        File {
+            inner: self.inner.file(name),
+            runtime: self.runtime.clone(),
+        }
+    }
+}
+
+/// A blocking counterpart of [`crate::storage::file::File`].
+pub struct File {
+    inner: crate::storage::file::File,
+    runtime: Arc<Runtime>,
+}
+
+impl File {
+    /// Returns the name of the file.
+    pub fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    /// Returns the name of the bucket containing the file.
+    pub fn bucket(&self) -> &str {
+        self.inner.bucket()
+    }
+
+    /// Blocking version of [`crate::storage::file::File::get_signed_url`].
+    pub fn get_signed_url(&self, options: GetSignedUrlOptions) -> Result<String, StorageError> {
+        self.runtime.block_on(self.inner.get_signed_url(options))
+    }
+
+    /// Blocking version of [`crate::storage::file::File::save`].
+    pub fn save(&self, body: impl Into<reqwest::Body>, mime_type: &str) -> Result<(), StorageError> {
+        self.runtime.block_on(self.inner.save(body, mime_type))
+    }
+
+    /// Blocking version of [`crate::storage::file::File::download`].
+    pub fn download(&self) -> Result<bytes::Bytes, StorageError> {
+        self.runtime.block_on(self.inner.download())
+    }
+
+    /// Blocking version of [`crate::storage::file::File::delete`].
+    pub fn delete(&self) -> Result<(), StorageError> {
+        self.runtime.block_on(self.inner.delete())
+    }
+
+    /// Blocking version of [`crate::storage::file::File::get_metadata`].
+    pub fn get_metadata(&self) -> Result<ObjectMetadata, StorageError> {
+        self.runtime.block_on(self.inner.get_metadata())
+    }
+
+    /// Blocking version of [`crate::storage::file::File::set_metadata`].
+    pub fn set_metadata(&self, metadata: &ObjectMetadata) -> Result<ObjectMetadata, StorageError> {
+        self.runtime.block_on(self.inner.set_metadata(metadata))
+    }
+}
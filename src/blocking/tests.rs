@@ -0,0 +1,83 @@
+use super::FirebaseApp;
+use crate::core::credential::StaticTokenCredential;
+use crate::core::emulator::EmulatorConfig;
+use httpmock::prelude::*;
+use serde_json::json;
+
+fn emulated_app(server: &MockServer) -> FirebaseApp {
+    let app = crate::FirebaseApp::from_credential(
+        StaticTokenCredential::new("unused").with_project_id("test-project"),
+    )
+    .unwrap()
+    .with_emulators(
+        EmulatorConfig::default()
+            .with_auth_host(server.address().to_string())
+            .with_firestore_host(server.address().to_string())
+            .with_storage_host(server.address().to_string()),
+    );
+    FirebaseApp::from_app(app)
+}
+
+#[cfg(feature = "auth")]
+#[test]
+fn test_blocking_get_user() {
+    let server = MockServer::start();
+    let app = emulated_app(&server);
+
+    let mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/identitytoolkit.googleapis.com/v1/projects/test-project/accounts:lookup");
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "users": [{ "localId": "test-uid", "emailVerified": false, "disabled": false }]
+            }));
+    });
+
+    let user = app.auth().get_user("test-uid").unwrap();
+    assert_eq!(user.local_id, "test-uid");
+    mock.assert();
+}
+
+#[cfg(all(feature = "firestore", feature = "storage"))]
+#[test]
+fn test_blocking_get_document_and_download() {
+    let server = MockServer::start();
+    let app = emulated_app(&server);
+
+    let doc_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/v1/projects/test-project/databases/(default)/documents/users/alice");
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "name": "projects/test-project/databases/(default)/documents/users/alice",
+                "fields": { "age": { "integerValue": "30" } },
+                "createTime": "2023-01-01T00:00:00Z",
+                "updateTime": "2023-01-01T00:00:00Z"
+            }));
+    });
+
+    let download_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/storage/v1/b/test-bucket/o/report.txt")
+            .query_param("alt", "media");
+        then.status(200).body("hello");
+    });
+
+    let firestore = app.firestore();
+    let snapshot = firestore.collection("users").doc("alice").get().unwrap();
+    let data: serde_json::Value = snapshot.data().unwrap().unwrap();
+    assert_eq!(data["age"], 30);
+
+    let bytes = app
+        .storage()
+        .bucket(Some("test-bucket"))
+        .file("report.txt")
+        .download()
+        .unwrap();
+    assert_eq!(&bytes[..], b"hello");
+
+    doc_mock.assert();
+    download_mock.assert();
+}
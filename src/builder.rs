@@ -0,0 +1,293 @@
+//! Builder for configuring a [`FirebaseApp`].
+
+use crate::core::credential::{
+    ApplicationDefaultCredential, Credential, CredentialError, ServiceAccountCredential,
+};
+use crate::core::emulator::EmulatorConfig;
+use crate::core::http::{HttpConfig, DEFAULT_MAX_RETRIES};
+use crate::core::middleware::AuthMiddleware;
+use crate::core::options::AppOptions;
+use crate::core::signer::Signer;
+use crate::core::telemetry::MetricsRecorder;
+use crate::core::token::TokenCache;
+use crate::core::transport::HttpTransport;
+use crate::{FirebaseApp, DEFAULT_APP_NAME};
+use reqwest::{Client, Proxy};
+use reqwest_middleware::Middleware;
+use reqwest_retry::policies::ExponentialBackoff;
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use yup_oauth2::ServiceAccountKey;
+
+/// Errors that can occur while building a [`FirebaseApp`].
+#[derive(Error, Debug)]
+pub enum BuildError {
+    /// The HTTP client could not be built (e.g., an invalid proxy or TLS configuration).
+    #[error("Failed to build HTTP client: {0}")]
+    HttpClientError(#[from] reqwest::Error),
+    /// No credential was provided and Application Default Credentials could not be loaded.
+    #[error("Failed to load credentials: {0}")]
+    CredentialError(#[from] CredentialError),
+    /// No project ID was found in the credential, `AppOptions::project_id`, the
+    /// `GOOGLE_CLOUD_PROJECT`, `GCLOUD_PROJECT` or `GCP_PROJECT` environment variables or
+    /// the metadata server.
+    #[error("Unable to determine the project ID; set AppOptions::project_id or GOOGLE_CLOUD_PROJECT")]
+    ProjectIdRequired,
+}
+
+/// A builder for [`FirebaseApp`] with shared HTTP, retry and credential settings.
+///
+/// All service clients created from the resulting app share one connection pool and
+/// inherit the configured timeouts, proxy, user agent, retry policy and middleware layers.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use firebase_admin_sdk::FirebaseApp;
+/// use std::time::Duration;
+///
+/// # async fn run() {
+/// let key = firebase_admin_sdk::yup_oauth2::read_service_account_key("service-account.json")
+///     .await
+///     .unwrap();
+///
+/// let app = FirebaseApp::builder()
+///     .service_account_key(key)
+///     .timeout(Duration::from_secs(30))
+///     .connect_timeout(Duration::from_secs(5))
+///     .max_retries(5)
+///     .build()
+///     .unwrap();
+/// # }
+/// ```
+#[derive(Default)]
+pub struct FirebaseAppBuilder {
+    credential: Option<Arc<dyn Credential>>,
+    client: Option<Client>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    proxy: Option<Proxy>,
+    user_agent: Option<String>,
+    retry_policy: Option<ExponentialBackoff>,
+    middlewares: Vec<Arc<dyn Middleware>>,
+    metrics: Option<Arc<dyn MetricsRecorder>>,
+    scopes: Option<Vec<String>>,
+    token_cache: Option<Arc<dyn TokenCache>>,
+    transport: Option<Arc<dyn HttpTransport>>,
+    emulators: Option<EmulatorConfig>,
+    options: AppOptions,
+    signer: Option<Arc<dyn Signer>>,
+}
+
+impl FirebaseAppBuilder {
+    /// Creates a new builder with default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Authenticates with the given service account key.
+    pub fn service_account_key(self, key: ServiceAccountKey) -> Self {
+        self.credential(ServiceAccountCredential::new(key))
+    }
+
+    /// Authenticates with the given credential.
+    ///
+    /// If no credential is set, Application Default Credentials are used.
+    pub fn credential(mut self, credential: impl Credential + 'static) -> Self {
+        self.credential = Some(Arc::new(credential));
+        self
+    }
+
+    /// Uses an existing `reqwest::Client` (and its connection pool) for all services.
+    ///
+    /// When set, the timeout, proxy and user agent settings of this builder are ignored
+    /// and must be configured on the provided client instead.
+    pub fn http_client(mut self, client: Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Sets the total timeout applied to each request.
+    ///
+    /// Note that this also bounds long-lived responses such as Firestore `listen()` streams.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the timeout for establishing connections.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Routes all requests through the given proxy.
+    pub fn proxy(mut self, proxy: Proxy) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Sets the `User-Agent` header sent with every request.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Sets the retry policy applied to transient failures.
+    pub fn retry_policy(mut self, policy: ExponentialBackoff) -> Self {
+        self.retry_policy = Some(policy);
+        self
+    }
+
+    /// Sets the maximum number of retries for transient failures, using exponential backoff.
+    ///
+    /// Pass `0` to disable retries.
+    pub fn max_retries(self, max_retries: u32) -> Self {
+        self.retry_policy(ExponentialBackoff::builder().build_with_max_retries(max_retries))
+    }
+
+    /// Adds an extra `reqwest_middleware` layer to every service client.
+    ///
+    /// Layers run after the retry layer, in the order they are added, and before the
+    /// `Authorization` header is injected.
+    pub fn with_middleware(mut self, middleware: impl Middleware) -> Self {
+        self.middlewares.push(Arc::new(middleware));
+        self
+    }
+
+    /// Reports call and error metrics for every API call to the given recorder.
+    pub fn metrics_recorder(mut self, recorder: impl MetricsRecorder + 'static) -> Self {
+        self.metrics = Some(Arc::new(recorder));
+        self
+    }
+
+    /// Overrides the OAuth2 scopes requested for access tokens.
+    ///
+    /// Defaults to the `cloud-platform` and `firebase` scopes.
+    pub fn scopes<I, S>(mut self, scopes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.scopes = Some(scopes.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Shares access tokens through the given cache (e.g., a `FileTokenCache` used by
+    /// several processes).
+    pub fn token_cache(mut self, cache: impl TokenCache + 'static) -> Self {
+        self.token_cache = Some(Arc::new(cache));
+        self
+    }
+
+    /// Sends all requests through the given transport instead of the HTTP client.
+    ///
+    /// Useful for testing code that uses the SDK against in-memory or recorded responses.
+    pub fn transport(mut self, transport: impl HttpTransport + 'static) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    /// Overrides the emulator hosts detected from the environment.
+    pub fn emulators(mut self, emulators: EmulatorConfig) -> Self {
+        self.emulators = Some(emulators);
+        self
+    }
+
+    /// Sets project, bucket, database and service account overrides.
+    pub fn options(mut self, options: AppOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Signs custom tokens, App Check tokens and signed URLs with the given signer (e.g., an
+    /// `IamSigner` when no private key is available locally).
+    ///
+    /// By default, the signer is chosen from the credential; see [`AuthMiddleware::signer`].
+    pub fn signer(mut self, signer: impl Signer + 'static) -> Self {
+        self.signer = Some(Arc::new(signer));
+        self
+    }
+
+    /// Builds the `FirebaseApp`.
+    ///
+    /// Like [`FirebaseApp::new`], no network requests are made until a service is used. Use
+    /// [`FirebaseAppBuilder::build_async`] to look the project ID up from the metadata server.
+    ///
+    /// Fails with [`BuildError::ProjectIdRequired`] if no project ID can be determined.
+    pub fn build(self) -> Result<FirebaseApp, BuildError> {
+        let credential = match self.credential {
+            Some(credential) => credential,
+            None => Arc::new(ApplicationDefaultCredential::new()?),
+        };
+
+        let client = match self.client {
+            Some(client) => client,
+            None => {
+                let mut builder = Client::builder();
+                if let Some(timeout) = self.timeout {
+                    builder = builder.timeout(timeout);
+                }
+                if let Some(timeout) = self.connect_timeout {
+                    builder = builder.connect_timeout(timeout);
+                }
+                if let Some(proxy) = self.proxy {
+                    builder = builder.proxy(proxy);
+                }
+                if let Some(user_agent) = self.user_agent {
+                    builder = builder.user_agent(user_agent);
+                }
+                builder.build()?
+            }
+        };
+
+        let http = HttpConfig {
+            client,
+            retry_policy: self.retry_policy.unwrap_or_else(|| {
+                ExponentialBackoff::builder().build_with_max_retries(DEFAULT_MAX_RETRIES)
+            }),
+            middlewares: self.middlewares,
+            metrics: self.metrics,
+            transport: self.transport,
+        };
+
+        let mut middleware = AuthMiddleware::from_credential(credential)
+            .with_emulators(self.emulators.unwrap_or_else(EmulatorConfig::from_env))
+            .with_http_config(http)
+            .with_options(self.options);
+        if let Some(scopes) = self.scopes {
+            middleware = middleware.with_scopes(scopes);
+        }
+        if let Some(cache) = self.token_cache {
+            middleware = middleware.with_token_cache(cache);
+        }
+        if let Some(signer) = self.signer {
+            middleware = middleware.with_signer(signer);
+        }
+        if middleware.project_id().is_empty() {
+            return Err(BuildError::ProjectIdRequired);
+        }
+
+        Ok(FirebaseApp {
+            name: DEFAULT_APP_NAME.to_string(),
+            middleware,
+        })
+    }
+
+    /// Builds the `FirebaseApp`, first looking up the project ID if the credential needs to
+    /// (e.g., metadata server credentials on Cloud Run without `GOOGLE_CLOUD_PROJECT`).
+    ///
+    /// Fails with [`BuildError::ProjectIdRequired`] if no project ID can be determined.
+    pub async fn build_async(mut self) -> Result<FirebaseApp, BuildError> {
+        let credential = match self.credential.take() {
+            Some(credential) => credential,
+            None => Arc::new(ApplicationDefaultCredential::new()?),
+        };
+        if self.options.project_id.is_none() && credential.project_id().is_none() {
+            credential.resolve_project_id().await?;
+        }
+        self.credential = Some(credential);
+        self.build()
+    }
+}
@@ -0,0 +1,584 @@
+//! Credential sources used to authorize Firebase API requests.
+//!
+//! A [`Credential`] produces OAuth2 access tokens for the Google APIs backing Firebase.
+//! The SDK ships implementations for service account keys, Application Default Credentials
+//! (a `GOOGLE_APPLICATION_CREDENTIALS` file, gcloud authorized-user refresh tokens or the
+//! GCE/Cloud Run metadata server), external accounts (workload identity federation) and
+//! static tokens for tests.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use firebase_admin_sdk::core::credential::ApplicationDefaultCredential;
+//! use firebase_admin_sdk::FirebaseApp;
+//!
+//! let credential = ApplicationDefaultCredential::new().unwrap();
+//! let app = FirebaseApp::from_credential(credential).unwrap();
+//! ```
+
+use async_trait::async_trait;
+use hyper_rustls::HttpsConnector;
+use hyper_util::client::legacy::connect::HttpConnector;
+use reqwest_middleware::ClientWithMiddleware;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use thiserror::Error;
+use tokio::sync::{Mutex, OnceCell};
+use yup_oauth2::authenticator::Authenticator;
+use yup_oauth2::authorized_user::AuthorizedUserSecret;
+use yup_oauth2::external_account::ExternalAccountSecret;
+use yup_oauth2::{
+    AuthorizedUserAuthenticator, ExternalAccountAuthenticator, ServiceAccountAuthenticator,
+    ServiceAccountKey,
+};
+
+/// Environment variable pointing to an Application Default Credentials JSON file.
+pub const GOOGLE_APPLICATION_CREDENTIALS_VAR: &str = "GOOGLE_APPLICATION_CREDENTIALS";
+
+/// The metadata server endpoint that issues tokens for the default service account.
+const METADATA_TOKEN_URL: &str =
+    "http://metadata.google.internal/computeMetadata/v1/instance/service-accounts/default/token";
+
+/// The metadata server endpoint that returns the ID of the project the instance runs in.
+const METADATA_PROJECT_ID_URL: &str =
+    "http://metadata.google.internal/computeMetadata/v1/project/project-id";
+
+/// How long metadata server requests may take before they are abandoned.
+const METADATA_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// How long before its expiry a token is considered stale and refreshed.
+const TOKEN_EXPIRY_SKEW: Duration = Duration::from_secs(60);
+
+/// Environment variables consulted, in order, for the project ID when the credential has none.
+pub const PROJECT_ID_VARS: [&str; 3] = ["GOOGLE_CLOUD_PROJECT", "GCLOUD_PROJECT", "GCP_PROJECT"];
+
+/// The concrete type of the Authenticator used by `yup-oauth2`.
+type AuthType = Authenticator<HttpsConnector<HttpConnector>>;
+
+/// Errors that can occur while loading credentials or fetching access tokens.
+#[derive(Error, Debug)]
+pub enum CredentialError {
+    /// Wrapper for `std::io::Error` (e.g., a credentials file could not be read).
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+    /// Wrapper for `serde_json::Error` (e.g., a malformed credentials file).
+    #[error("Invalid credentials file: {0}")]
+    ParseError(#[from] serde_json::Error),
+    /// Wrapper for `reqwest::Error` (e.g., the metadata server is unreachable).
+    #[error("HTTP Request failed: {0}")]
+    RequestError(#[from] reqwest::Error),
+    /// Wrapper for `reqwest_middleware::Error`.
+    #[error("Middleware error: {0}")]
+    MiddlewareError(#[from] reqwest_middleware::Error),
+    /// Wrapper for `yup_oauth2::Error`.
+    #[error("OAuth2 error: {0}")]
+    OAuthError(#[from] yup_oauth2::Error),
+    /// The credentials file has a `type` this SDK does not support.
+    #[error("Unsupported credential type: {0}")]
+    UnsupportedType(String),
+    /// The token endpoint responded without an access token.
+    #[error("No access token found in response")]
+    MissingToken,
+}
+
+/// An OAuth2 access token together with its expiry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccessToken {
+    /// The bearer token value.
+    pub token: String,
+    /// When the token expires, if known.
+    pub expires_at: Option<SystemTime>,
+}
+
+impl AccessToken {
+    /// Returns `true` if the token is not due to expire within the next minute.
+    ///
+    /// Tokens without a known expiry are always considered fresh.
+    pub fn is_fresh(&self) -> bool {
+        self.expires_at
+            .is_none_or(|exp| exp > SystemTime::now() + TOKEN_EXPIRY_SKEW)
+    }
+}
+
+/// A source of OAuth2 access tokens for Firebase API requests.
+#[async_trait]
+pub trait Credential: Send + Sync {
+    /// Returns an access token valid for the given scopes.
+    ///
+    /// Implementations are expected to cache tokens and only refresh them when they expire.
+    async fn access_token(&self, scopes: &[&str]) -> Result<AccessToken, CredentialError>;
+
+    /// Returns a newly issued access token, bypassing any cached token.
+    ///
+    /// Called after an API rejected the current token with `401 Unauthorized`.
+    /// The default implementation delegates to [`Credential::access_token`].
+    async fn refresh_token(&self, scopes: &[&str]) -> Result<AccessToken, CredentialError> {
+        self.access_token(scopes).await
+    }
+
+    /// Returns the project ID associated with this credential, if it carries one.
+    ///
+    /// This must not block; credentials that look the project ID up over the network only
+    /// report it here after [`Credential::resolve_project_id`] has found it.
+    fn project_id(&self) -> Option<String> {
+        None
+    }
+
+    /// Looks up the project ID, contacting the network if the credential needs to.
+    ///
+    /// Returns `Ok(None)` when there is no project ID to find. The default implementation
+    /// returns [`Credential::project_id`].
+    async fn resolve_project_id(&self) -> Result<Option<String>, CredentialError> {
+        Ok(self.project_id())
+    }
+
+    /// Returns the service account key backing this credential, if any.
+    ///
+    /// Operations that sign locally (custom tokens, signed URLs) require a key.
+    fn service_account_key(&self) -> Option<&ServiceAccountKey> {
+        None
+    }
+}
+
+#[async_trait]
+impl<T: Credential + ?Sized> Credential for std::sync::Arc<T> {
+    async fn access_token(&self, scopes: &[&str]) -> Result<AccessToken, CredentialError> {
+        (**self).access_token(scopes).await
+    }
+
+    async fn refresh_token(&self, scopes: &[&str]) -> Result<AccessToken, CredentialError> {
+        (**self).refresh_token(scopes).await
+    }
+
+    fn project_id(&self) -> Option<String> {
+        (**self).project_id()
+    }
+
+    async fn resolve_project_id(&self) -> Result<Option<String>, CredentialError> {
+        (**self).resolve_project_id().await
+    }
+
+    fn service_account_key(&self) -> Option<&ServiceAccountKey> {
+        (**self).service_account_key()
+    }
+}
+
+/// Converts a `yup-oauth2` token into an [`AccessToken`].
+fn convert_token(token: yup_oauth2::AccessToken) -> Result<AccessToken, CredentialError> {
+    Ok(AccessToken {
+        token: token.token().ok_or(CredentialError::MissingToken)?.to_string(),
+        expires_at: token.expiration_time().map(SystemTime::from),
+    })
+}
+
+/// Credential backed by a service account key.
+///
+/// The underlying authenticator is built lazily on the first token request.
+pub struct ServiceAccountCredential {
+    key: ServiceAccountKey,
+    authenticator: OnceCell<AuthType>,
+}
+
+impl ServiceAccountCredential {
+    /// Creates a credential from a parsed service account key.
+    pub fn new(key: ServiceAccountKey) -> Self {
+        Self {
+            key,
+            authenticator: OnceCell::new(),
+        }
+    }
+
+    /// Reads a service account key from a JSON file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, CredentialError> {
+        let key = serde_json::from_slice(&std::fs::read(path)?)?;
+        Ok(Self::new(key))
+    }
+
+    /// Returns the authenticator, building it on first use.
+    async fn authenticator(&self) -> Result<&AuthType, CredentialError> {
+        let auth = self
+            .authenticator
+            .get_or_try_init(|| async { ServiceAccountAuthenticator::builder(self.key.clone()).build().await })
+            .await?;
+        Ok(auth)
+    }
+}
+
+#[async_trait]
+impl Credential for ServiceAccountCredential {
+    async fn access_token(&self, scopes: &[&str]) -> Result<AccessToken, CredentialError> {
+        convert_token(self.authenticator().await?.token(scopes).await?)
+    }
+
+    async fn refresh_token(&self, scopes: &[&str]) -> Result<AccessToken, CredentialError> {
+        convert_token(self.authenticator().await?.force_refreshed_token(scopes).await?)
+    }
+
+    fn project_id(&self) -> Option<String> {
+        self.key.project_id.clone()
+    }
+
+    fn service_account_key(&self) -> Option<&ServiceAccountKey> {
+        Some(&self.key)
+    }
+}
+
+/// Credential backed by a gcloud authorized-user refresh token
+/// (as written by `gcloud auth application-default login`).
+pub struct AuthorizedUserCredential {
+    secret: AuthorizedUserSecret,
+    quota_project_id: Option<String>,
+    authenticator: OnceCell<AuthType>,
+}
+
+impl AuthorizedUserCredential {
+    /// Creates a credential from a parsed authorized-user secret.
+    pub fn new(secret: AuthorizedUserSecret) -> Self {
+        Self {
+            secret,
+            quota_project_id: None,
+            authenticator: OnceCell::new(),
+        }
+    }
+
+    /// Returns the authenticator, building it on first use.
+    async fn authenticator(&self) -> Result<&AuthType, CredentialError> {
+        let auth = self
+            .authenticator
+            .get_or_try_init(|| async { AuthorizedUserAuthenticator::builder(self.secret.clone()).build().await })
+            .await?;
+        Ok(auth)
+    }
+}
+
+#[async_trait]
+impl Credential for AuthorizedUserCredential {
+    async fn access_token(&self, scopes: &[&str]) -> Result<AccessToken, CredentialError> {
+        convert_token(self.authenticator().await?.token(scopes).await?)
+    }
+
+    async fn refresh_token(&self, scopes: &[&str]) -> Result<AccessToken, CredentialError> {
+        convert_token(self.authenticator().await?.force_refreshed_token(scopes).await?)
+    }
+
+    fn project_id(&self) -> Option<String> {
+        self.quota_project_id.clone()
+    }
+}
+
+/// Credential backed by an external account (workload identity federation).
+pub struct ExternalAccountCredential {
+    secret: ExternalAccountSecret,
+    authenticator: OnceCell<AuthType>,
+}
+
+impl ExternalAccountCredential {
+    /// Creates a credential from a parsed external account configuration.
+    pub fn new(secret: ExternalAccountSecret) -> Self {
+        Self {
+            secret,
+            authenticator: OnceCell::new(),
+        }
+    }
+
+    /// Returns the authenticator, building it on first use.
+    async fn authenticator(&self) -> Result<&AuthType, CredentialError> {
+        let auth = self
+            .authenticator
+            .get_or_try_init(|| async { ExternalAccountAuthenticator::builder(self.secret.clone()).build().await })
+            .await?;
+        Ok(auth)
+    }
+}
+
+#[async_trait]
+impl Credential for ExternalAccountCredential {
+    async fn access_token(&self, scopes: &[&str]) -> Result<AccessToken, CredentialError> {
+        convert_token(self.authenticator().await?.token(scopes).await?)
+    }
+
+    async fn refresh_token(&self, scopes: &[&str]) -> Result<AccessToken, CredentialError> {
+        convert_token(self.authenticator().await?.force_refreshed_token(scopes).await?)
+    }
+}
+
+/// Credential backed by the GCE / Cloud Run / GKE metadata server.
+///
+/// Tokens are cached and refreshed one minute before they expire.
+/// The project ID is taken from the `GOOGLE_CLOUD_PROJECT`, `GCLOUD_PROJECT` or `GCP_PROJECT`
+/// environment variables, or else fetched once from the metadata server by
+/// [`Credential::resolve_project_id`] (e.g., in `FirebaseAppBuilder::build_async`).
+pub struct MetadataServerCredential {
+    client: reqwest::Client,
+    token_url: String,
+    project_id_url: String,
+    cached: Mutex<Option<AccessToken>>,
+    project_id: OnceCell<Option<String>>,
+}
+
+#[derive(Deserialize)]
+struct MetadataTokenResponse {
+    access_token: String,
+    expires_in: u64,
+}
+
+impl Default for MetadataServerCredential {
+    fn default() -> Self {
+        Self::with_token_url(METADATA_TOKEN_URL)
+    }
+}
+
+impl MetadataServerCredential {
+    /// Creates a credential that fetches tokens from the default metadata server.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a credential that fetches tokens from a custom metadata token URL.
+    pub fn with_token_url(token_url: impl Into<String>) -> Self {
+        Self {
+            client: metadata_client(),
+            token_url: token_url.into(),
+            project_id_url: METADATA_PROJECT_ID_URL.to_string(),
+            cached: Mutex::new(None),
+            project_id: OnceCell::new(),
+        }
+    }
+
+    /// Sets the metadata URL the project ID is fetched from.
+    pub fn with_project_id_url(mut self, project_id_url: impl Into<String>) -> Self {
+        self.project_id_url = project_id_url.into();
+        self
+    }
+}
+
+impl MetadataServerCredential {
+    async fn fetch_token(&self, scopes: &[&str]) -> Result<AccessToken, CredentialError> {
+        let response = self
+            .client
+            .get(&self.token_url)
+            .query(&[("scopes", scopes.join(","))])
+            .header("Metadata-Flavor", "Google")
+            .send()
+            .await?
+            .error_for_status()?;
+
+        let body: MetadataTokenResponse = response.json().await?;
+        Ok(AccessToken {
+            token: body.access_token,
+            expires_at: Some(SystemTime::now() + Duration::from_secs(body.expires_in)),
+        })
+    }
+}
+
+#[async_trait]
+impl Credential for MetadataServerCredential {
+    async fn access_token(&self, scopes: &[&str]) -> Result<AccessToken, CredentialError> {
+        // Holding the lock while fetching lets concurrent callers share one refresh.
+        let mut cached = self.cached.lock().await;
+        if let Some(token) = cached.as_ref().filter(|t| t.is_fresh()) {
+            return Ok(token.clone());
+        }
+        let token = self.fetch_token(scopes).await?;
+        *cached = Some(token.clone());
+        Ok(token)
+    }
+
+    async fn refresh_token(&self, scopes: &[&str]) -> Result<AccessToken, CredentialError> {
+        let mut cached = self.cached.lock().await;
+        let token = self.fetch_token(scopes).await?;
+        *cached = Some(token.clone());
+        Ok(token)
+    }
+
+    fn project_id(&self) -> Option<String> {
+        project_id_from_env().or_else(|| self.project_id.get().cloned().flatten())
+    }
+
+    async fn resolve_project_id(&self) -> Result<Option<String>, CredentialError> {
+        if let Some(project_id) = project_id_from_env() {
+            return Ok(Some(project_id));
+        }
+        let client = ClientWithMiddleware::from(self.client.clone());
+        let project_id = self
+            .project_id
+            .get_or_try_init(|| fetch_metadata_value(&client, &self.project_id_url))
+            .await?;
+        Ok(project_id.clone())
+    }
+}
+
+/// Credential that always returns the same token. Useful for tests and short-lived scripts.
+#[derive(Debug, Clone)]
+pub struct StaticTokenCredential {
+    token: String,
+    project_id: Option<String>,
+}
+
+impl StaticTokenCredential {
+    /// Creates a credential that always returns `token`.
+    pub fn new(token: impl Into<String>) -> Self {
+        Self {
+            token: token.into(),
+            project_id: None,
+        }
+    }
+
+    /// Sets the project ID reported by this credential.
+    pub fn with_project_id(mut self, project_id: impl Into<String>) -> Self {
+        self.project_id = Some(project_id.into());
+        self
+    }
+}
+
+#[async_trait]
+impl Credential for StaticTokenCredential {
+    async fn access_token(&self, _scopes: &[&str]) -> Result<AccessToken, CredentialError> {
+        Ok(AccessToken {
+            token: self.token.clone(),
+            expires_at: None,
+        })
+    }
+
+    fn project_id(&self) -> Option<String> {
+        self.project_id.clone()
+    }
+}
+
+/// Application Default Credentials.
+///
+/// Credentials are resolved in the following order:
+///
+/// 1. The JSON file named by `GOOGLE_APPLICATION_CREDENTIALS` (service account,
+///    authorized user or external account).
+/// 2. The gcloud well-known file written by `gcloud auth application-default login`.
+/// 3. The GCE / Cloud Run metadata server.
+pub struct ApplicationDefaultCredential {
+    inner: Box<dyn Credential>,
+}
+
+#[derive(Deserialize)]
+struct CredentialsFileType {
+    #[serde(rename = "type")]
+    key_type: String,
+    quota_project_id: Option<String>,
+}
+
+impl ApplicationDefaultCredential {
+    /// Resolves Application Default Credentials from the environment.
+    ///
+    /// This method only reads local files; no network requests are made until a token is needed.
+    pub fn new() -> Result<Self, CredentialError> {
+        if let Some(path) = std::env::var_os(GOOGLE_APPLICATION_CREDENTIALS_VAR) {
+            return Self::from_file(path);
+        }
+
+        if let Some(path) = gcloud_well_known_file().filter(|p| p.is_file()) {
+            return Self::from_file(path);
+        }
+
+        Ok(Self {
+            inner: Box::new(MetadataServerCredential::new()),
+        })
+    }
+
+    /// Loads credentials from a JSON file, detecting its `type`.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, CredentialError> {
+        Self::from_json(&std::fs::read(path)?)
+    }
+
+    /// Parses credentials from JSON, detecting its `type`.
+    pub fn from_json(json: &[u8]) -> Result<Self, CredentialError> {
+        let file_type: CredentialsFileType = serde_json::from_slice(json)?;
+
+        let inner: Box<dyn Credential> = match file_type.key_type.as_str() {
+            "service_account" => {
+                Box::new(ServiceAccountCredential::new(serde_json::from_slice(json)?))
+            }
+            "authorized_user" => {
+                let mut credential = AuthorizedUserCredential::new(serde_json::from_slice(json)?);
+                credential.quota_project_id = file_type.quota_project_id;
+                Box::new(credential)
+            }
+            "external_account" => {
+                Box::new(ExternalAccountCredential::new(serde_json::from_slice(json)?))
+            }
+            other => return Err(CredentialError::UnsupportedType(other.to_string())),
+        };
+
+        Ok(Self { inner })
+    }
+}
+
+#[async_trait]
+impl Credential for ApplicationDefaultCredential {
+    async fn access_token(&self, scopes: &[&str]) -> Result<AccessToken, CredentialError> {
+        self.inner.access_token(scopes).await
+    }
+
+    async fn refresh_token(&self, scopes: &[&str]) -> Result<AccessToken, CredentialError> {
+        self.inner.refresh_token(scopes).await
+    }
+
+    fn project_id(&self) -> Option<String> {
+        self.inner.project_id()
+    }
+
+    async fn resolve_project_id(&self) -> Result<Option<String>, CredentialError> {
+        self.inner.resolve_project_id().await
+    }
+
+    fn service_account_key(&self) -> Option<&ServiceAccountKey> {
+        self.inner.service_account_key()
+    }
+}
+
+/// Returns the path of the gcloud Application Default Credentials file.
+fn gcloud_well_known_file() -> Option<PathBuf> {
+    let config_dir = if let Some(dir) = std::env::var_os("CLOUDSDK_CONFIG") {
+        PathBuf::from(dir)
+    } else if cfg!(windows) {
+        PathBuf::from(std::env::var_os("APPDATA")?).join("gcloud")
+    } else {
+        PathBuf::from(std::env::var_os("HOME")?)
+            .join(".config")
+            .join("gcloud")
+    };
+    Some(config_dir.join("application_default_credentials.json"))
+}
+
+/// Resolves the project ID from the standard environment variables.
+pub(crate) fn project_id_from_env() -> Option<String> {
+    PROJECT_ID_VARS
+        .iter()
+        .find_map(|var| std::env::var(var).ok().filter(|v| !v.is_empty()))
+}
+
+/// Builds the HTTP client used for metadata server requests.
+pub(crate) fn metadata_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(METADATA_TIMEOUT)
+        .build()
+        .unwrap_or_default()
+}
+
+/// Reads a value from the metadata server.
+///
+/// Returns `Ok(None)` when there is no metadata server (it cannot be reached or does not
+/// know the value), and an error for failures that may be transient.
+pub(crate) async fn fetch_metadata_value(
+    client: &ClientWithMiddleware,
+    url: &str,
+) -> reqwest_middleware::Result<Option<String>> {
+    let response = match client.get(url).header("Metadata-Flavor", "Google").send().await {
+        Ok(response) => response,
+        Err(reqwest_middleware::Error::Reqwest(e)) if e.is_connect() => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    let value = response.error_for_status()?.text().await?.trim().to_string();
+    Ok(Some(value).filter(|v| !v.is_empty()))
+}
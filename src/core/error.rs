@@ -0,0 +1,145 @@
+//! Structured error model shared by all service clients.
+
+use super::{FirebaseErrorResponse, FirebaseSubError};
+use serde_json::Value;
+use std::fmt;
+
+const FCM_ERROR_TYPE: &str = "type.googleapis.com/google.firebase.fcm.v1.FcmError";
+const ERROR_INFO_TYPE: &str = "type.googleapis.com/google.rpc.ErrorInfo";
+
+/// An error returned by a Firebase or Google Cloud API.
+///
+/// Service error enums (e.g., `AuthError`, `MessagingError`) map well-known codes to typed
+/// variants and carry this value for everything else, so callers can match on the canonical
+/// status or service code instead of parsing error messages.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FirebaseError {
+    /// The HTTP status code of the response.
+    pub http_status: u16,
+    /// The canonical gRPC status (e.g., `NOT_FOUND`, `ABORTED`).
+    ///
+    /// Taken from the response body when present, otherwise derived from the HTTP status.
+    pub status: String,
+    /// The service-specific error code (e.g., `EMAIL_EXISTS`, `UNREGISTERED`, `QUOTA_EXCEEDED`).
+    pub code: Option<String>,
+    /// The human readable error message.
+    pub message: String,
+    /// Additional error details returned by the API.
+    pub sub_errors: Vec<FirebaseSubError>,
+}
+
+impl FirebaseError {
+    /// Returns `true` if the request may succeed when retried (e.g., `UNAVAILABLE`,
+    /// `RESOURCE_EXHAUSTED`, `ABORTED` or a 5xx response).
+    pub fn is_retryable(&self) -> bool {
+        matches!(self.http_status, 408 | 429 | 500 | 502 | 503 | 504)
+            || matches!(
+                self.status.as_str(),
+                "ABORTED" | "DEADLINE_EXCEEDED" | "INTERNAL" | "RESOURCE_EXHAUSTED" | "UNAVAILABLE"
+            )
+    }
+
+    /// Returns `true` if the service-specific code matches `code`.
+    pub fn has_code(&self, code: &str) -> bool {
+        self.code.as_deref() == Some(code)
+    }
+
+    /// Reads an unsuccessful response into a `FirebaseError`.
+    ///
+    /// `default_msg` is used as the message when the body is not a Google API error payload.
+    pub async fn from_response(response: reqwest::Response, default_msg: &str) -> Self {
+        let http_status = response.status().as_u16();
+        let body = response.text().await.unwrap_or_default();
+        Self::from_body(http_status, &body, default_msg)
+    }
+
+    /// Parses an error payload received with the given HTTP status.
+    pub(crate) fn from_body(http_status: u16, body: &str, default_msg: &str) -> Self {
+        let response = match serde_json::from_str::<FirebaseErrorResponse>(body) {
+            Ok(response) => response,
+            Err(_) => {
+                let body = body.trim();
+                let message = if body.is_empty() {
+                    default_msg.to_string()
+                } else {
+                    format!("{}: {}", default_msg, body)
+                };
+                return Self {
+                    http_status,
+                    status: status_from_http(http_status).to_string(),
+                    code: None,
+                    message,
+                    sub_errors: Vec::new(),
+                };
+            }
+        };
+
+        let error = response.error;
+        let details = error.details.unwrap_or_default();
+        let code = code_from_details(&details).or_else(|| code_from_message(&error.message));
+
+        Self {
+            http_status,
+            status: error
+                .status
+                .unwrap_or_else(|| status_from_http(http_status).to_string()),
+            code,
+            message: error.message,
+            sub_errors: error.errors.unwrap_or_default(),
+        }
+    }
+}
+
+impl fmt::Display for FirebaseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (code: {})", self.message, self.http_status)
+    }
+}
+
+impl std::error::Error for FirebaseError {}
+
+/// Maps an HTTP status to the canonical gRPC status used by Google APIs.
+fn status_from_http(http_status: u16) -> &'static str {
+    match http_status {
+        400 => "INVALID_ARGUMENT",
+        401 => "UNAUTHENTICATED",
+        403 => "PERMISSION_DENIED",
+        404 => "NOT_FOUND",
+        409 => "ABORTED",
+        412 => "FAILED_PRECONDITION",
+        416 => "OUT_OF_RANGE",
+        429 => "RESOURCE_EXHAUSTED",
+        499 => "CANCELLED",
+        500 => "INTERNAL",
+        501 => "UNIMPLEMENTED",
+        503 => "UNAVAILABLE",
+        504 => "DEADLINE_EXCEEDED",
+        _ => "UNKNOWN",
+    }
+}
+
+/// Extracts the service code from FCM error details or a `google.rpc.ErrorInfo` reason.
+fn code_from_details(details: &[Value]) -> Option<String> {
+    let find = |type_url: &str, field: &str| {
+        details
+            .iter()
+            .filter(|d| d.get("@type").and_then(Value::as_str) == Some(type_url))
+            .find_map(|d| d.get(field).and_then(Value::as_str))
+            .map(str::to_string)
+    };
+
+    find(FCM_ERROR_TYPE, "errorCode").or_else(|| find(ERROR_INFO_TYPE, "reason"))
+}
+
+/// Extracts the service code from Identity Toolkit style messages such as
+/// `"EMAIL_EXISTS"` or `"WEAK_PASSWORD : Password should be at least 6 characters"`.
+fn code_from_message(message: &str) -> Option<String> {
+    let candidate = message.split(':').next()?.trim();
+    let is_code = !candidate.is_empty()
+        && candidate
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+        && candidate.chars().any(|c| c.is_ascii_uppercase());
+
+    is_code.then(|| candidate.to_string())
+}
@@ -0,0 +1,82 @@
+//! Shared HTTP configuration for all service clients.
+
+use crate::core::middleware::AuthMiddleware;
+use crate::core::telemetry::{AttemptMiddleware, MetricsRecorder, TelemetryMiddleware};
+use crate::core::transport::{HttpTransport, TransportMiddleware};
+use reqwest::Client;
+use reqwest_middleware::{ClientBuilder, ClientWithMiddleware, Middleware};
+use reqwest_retry::{policies::ExponentialBackoff, RetryTransientMiddleware};
+use std::sync::Arc;
+
+/// The number of retries applied to transient failures by default.
+pub const DEFAULT_MAX_RETRIES: u32 = 3;
+
+/// HTTP settings shared by every service client created from the same `FirebaseApp`.
+///
+/// Service clients built from this configuration share one connection pool and apply the
+/// middleware stack in this order: telemetry, retry policy, extra layers, authentication.
+/// Requests are then sent by the custom transport, if any, or by `client`.
+#[derive(Clone)]
+pub struct HttpConfig {
+    /// The underlying HTTP client (connection pool, timeouts, proxy, user agent).
+    pub client: Client,
+    /// The retry policy applied to transient failures.
+    pub retry_policy: ExponentialBackoff,
+    /// Extra middleware layers, applied after the retry layer and before authentication.
+    pub middlewares: Vec<Arc<dyn Middleware>>,
+    /// Receives call and error metrics for every API call.
+    pub metrics: Option<Arc<dyn MetricsRecorder>>,
+    /// Replaces `client` as the network layer, e.g. with an in-memory transport in tests.
+    pub transport: Option<Arc<dyn HttpTransport>>,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            client: Client::new(),
+            retry_policy: ExponentialBackoff::builder().build_with_max_retries(DEFAULT_MAX_RETRIES),
+            middlewares: Vec::new(),
+            metrics: None,
+            transport: None,
+        }
+    }
+}
+
+/// Builds the middleware-wrapped client used by a service.
+///
+/// `service` names the service in tracing spans and metrics (e.g., `auth`, `firestore`).
+pub(crate) fn build_client(middleware: &AuthMiddleware, service: &'static str) -> ClientWithMiddleware {
+    build(middleware, service, true)
+}
+
+/// Builds a client like [`build_client`] that does not authenticate requests, for public
+/// endpoints such as token verification keys.
+#[cfg(feature = "auth")]
+pub(crate) fn build_public_client(middleware: &AuthMiddleware, service: &'static str) -> ClientWithMiddleware {
+    build(middleware, service, false)
+}
+
+fn build(middleware: &AuthMiddleware, service: &'static str, authenticate: bool) -> ClientWithMiddleware {
+    let config = middleware.http_config();
+
+    let mut builder = ClientBuilder::new(config.client.clone())
+        .with(TelemetryMiddleware::new(
+            service,
+            middleware.project_id().to_string(),
+            config.metrics.clone(),
+        ))
+        .with(RetryTransientMiddleware::new_with_policy(config.retry_policy))
+        .with(AttemptMiddleware);
+
+    for layer in &config.middlewares {
+        builder = builder.with_arc(layer.clone());
+    }
+
+    if authenticate {
+        builder = builder.with(middleware.clone());
+    }
+    if let Some(transport) = &config.transport {
+        builder = builder.with(TransportMiddleware(transport.clone()));
+    }
+    builder.build()
+}
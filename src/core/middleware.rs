@@ -1,33 +1,66 @@
-use reqwest::{Request, Response, header};
+use crate::core::credential::{
+    project_id_from_env, AccessToken, Credential, CredentialError, ServiceAccountCredential,
+};
+use crate::core::emulator::{EmulatorConfig, EMULATOR_TOKEN};
+use crate::core::http::HttpConfig;
+use crate::core::options::AppOptions;
+use crate::core::signer::{IamSigner, ServiceAccountSigner, Signer, SignerError};
+use crate::core::telemetry::TokenLatency;
+use crate::core::token::{TokenCache, DEFAULT_SCOPES};
+use reqwest::{header, Request, Response, StatusCode};
 use reqwest_middleware::{Middleware, Next};
-use tokio::sync::OnceCell;
-use yup_oauth2::{ServiceAccountAuthenticator, ServiceAccountKey};
-use yup_oauth2::authenticator::Authenticator;
-use hyper_rustls::HttpsConnector;
-use hyper_util::client::legacy::connect::HttpConnector;
+use yup_oauth2::ServiceAccountKey;
 use http::Extensions;
-use std::sync::Arc;
-
-/// The concrete type of the Authenticator used by `yup-oauth2`.
-type AuthType = Authenticator<HttpsConnector<HttpConnector>>;
+#[cfg(feature = "auth")]
+use crate::auth::keys::KeySource;
+#[cfg(feature = "auth")]
+use std::collections::HashMap;
+#[cfg(feature = "auth")]
+use std::sync::Mutex;
+use std::sync::{Arc, OnceLock};
+use std::time::Instant;
 
 /// A middleware that handles OAuth2 authentication for Firebase requests.
 ///
 /// This middleware intercepts outgoing requests, obtains a valid OAuth2 Bearer token
-/// using the service account credentials, and injects it into the `Authorization` header.
+/// from the configured [`Credential`], and injects it into the `Authorization` header.
 ///
 /// # Lazy Initialization
 ///
-/// The `Authenticator` is initialized lazily using `tokio::sync::OnceCell` upon the first request.
+/// Credentials only contact their token endpoint upon the first request.
 /// This allows the `FirebaseApp` constructor to remain synchronous.
+///
+/// # Re-authentication
+///
+/// If an API responds with `401 Unauthorized`, a fresh token is requested and the request
+/// is retried once.
 #[derive(Clone)]
 pub struct AuthMiddleware {
-    /// The service account key used to create the authenticator.
-    pub key: ServiceAccountKey,
-    /// A lazy-initialized authenticator instance.
-    authenticator: Arc<OnceCell<AuthType>>,
+    /// The credential used to obtain access tokens.
+    credential: Arc<dyn Credential>,
+    /// The project ID resolved from the credential or the environment.
+    project_id: String,
     /// Optional Tenant ID for multi-tenancy.
     tenant_id: Option<String>,
+    /// Emulator hosts that service clients should target instead of production.
+    emulators: Arc<EmulatorConfig>,
+    /// Whether requests are sent to an emulator with the `owner` token.
+    emulated: bool,
+    /// HTTP settings shared by the service clients.
+    http: Arc<HttpConfig>,
+    /// Per-app overrides (project, bucket, database, service account).
+    options: Arc<AppOptions>,
+    /// OAuth2 scopes requested for access tokens.
+    scopes: Arc<Vec<String>>,
+    /// Optional cache in front of the credential, e.g. shared between processes.
+    token_cache: Option<Arc<dyn TokenCache>>,
+    /// Signer for tokens and URLs; chosen from the credential when not set.
+    signer: Option<Arc<dyn Signer>>,
+    /// The signer chosen from the credential, resolved on first use.
+    default_signer: Arc<OnceLock<Arc<dyn Signer>>>,
+    /// Token verification keys by URL, shared by the clients of this app.
+    #[cfg(feature = "auth")]
+    key_sources: Arc<Mutex<HashMap<&'static str, Arc<dyn KeySource>>>>,
 }
 
 impl AuthMiddleware {
@@ -37,43 +70,271 @@ impl AuthMiddleware {
     ///
     /// * `key` - The service account credentials.
     pub fn new(key: ServiceAccountKey) -> Self {
+        Self::from_credential(Arc::new(ServiceAccountCredential::new(key)))
+    }
+
+    /// Creates a new `AuthMiddleware` instance backed by an arbitrary credential.
+    ///
+    /// If the credential does not carry a project ID, it is read from the
+    /// `GOOGLE_CLOUD_PROJECT`, `GCLOUD_PROJECT` or `GCP_PROJECT` environment variables.
+    pub fn from_credential(credential: Arc<dyn Credential>) -> Self {
+        let project_id = credential
+            .project_id()
+            .or_else(project_id_from_env)
+            .unwrap_or_default();
+
         Self {
-            key,
-            authenticator: Arc::new(OnceCell::new()),
+            credential,
+            project_id,
             tenant_id: None,
+            emulators: Arc::new(EmulatorConfig::default()),
+            emulated: false,
+            http: Arc::new(HttpConfig::default()),
+            options: Arc::new(AppOptions::default()),
+            scopes: Arc::new(DEFAULT_SCOPES.iter().map(|s| s.to_string()).collect()),
+            token_cache: None,
+            signer: None,
+            default_signer: Arc::new(OnceLock::new()),
+            #[cfg(feature = "auth")]
+            key_sources: Arc::default(),
         }
     }
 
     /// Creates a new `AuthMiddleware` instance with a specific Tenant ID.
     pub fn with_tenant(&self, tenant_id: &str) -> Self {
         Self {
-            key: self.key.clone(),
-            authenticator: self.authenticator.clone(),
             tenant_id: Some(tenant_id.to_string()),
+            ..self.clone()
         }
     }
 
+    /// Gets the project ID used by service clients.
+    ///
+    /// An `AppOptions::project_id` override takes precedence over the credential's project.
+    pub fn project_id(&self) -> &str {
+        self.options
+            .project_id
+            .as_deref()
+            .unwrap_or(&self.project_id)
+    }
+
+    /// Sets the per-app overrides used by service clients built from this middleware.
+    pub fn with_options(mut self, options: AppOptions) -> Self {
+        self.options = Arc::new(options);
+        self.default_signer = Arc::new(OnceLock::new());
+        self
+    }
+
+    /// Gets the per-app overrides.
+    pub fn options(&self) -> &AppOptions {
+        &self.options
+    }
+
+    /// Gets the service account key backing the credential, if any.
+    pub fn service_account_key(&self) -> Option<&ServiceAccountKey> {
+        self.credential.service_account_key()
+    }
+
+    /// Gets the credential used to obtain access tokens.
+    pub fn credential(&self) -> &Arc<dyn Credential> {
+        &self.credential
+    }
+
     /// Gets the current Tenant ID.
     pub fn tenant_id(&self) -> Option<String> {
         self.tenant_id.clone()
     }
 
-    /// Retrieves a valid OAuth2 token, initializing the authenticator if necessary.
-    async fn get_token(&self) -> Result<String, anyhow::Error> {
-        let key = self.key.clone();
-        let auth = self.authenticator.get_or_try_init(|| async move {
-            ServiceAccountAuthenticator::builder(key)
-                .build()
-                .await
-                .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))
-        }).await?;
+    /// Sets the emulator hosts that service clients built from this middleware should target.
+    pub fn with_emulators(mut self, emulators: EmulatorConfig) -> Self {
+        self.emulators = Arc::new(emulators);
+        self
+    }
+
+    /// Gets the configured emulator hosts.
+    pub fn emulators(&self) -> &EmulatorConfig {
+        &self.emulators
+    }
+
+    /// Sets the HTTP settings used by service clients built from this middleware.
+    pub fn with_http_config(mut self, http: HttpConfig) -> Self {
+        self.http = Arc::new(http);
+        self.default_signer = Arc::new(OnceLock::new());
+        #[cfg(feature = "auth")]
+        {
+            self.key_sources = Arc::default();
+        }
+        self
+    }
+
+    /// Gets the HTTP settings used by service clients.
+    pub fn http_config(&self) -> &HttpConfig {
+        &self.http
+    }
+
+    /// Creates a copy of this middleware that authenticates with the emulator `owner` token.
+    #[cfg(any(feature = "auth", feature = "firestore", feature = "storage"))]
+    pub(crate) fn for_emulator(&self) -> Self {
+        Self {
+            emulated: true,
+            default_signer: Arc::new(OnceLock::new()),
+            ..self.clone()
+        }
+    }
+
+    /// Sets the OAuth2 scopes requested for access tokens.
+    ///
+    /// Defaults to [`DEFAULT_SCOPES`].
+    pub fn with_scopes(mut self, scopes: Vec<String>) -> Self {
+        self.scopes = Arc::new(scopes);
+        self.default_signer = Arc::new(OnceLock::new());
+        self
+    }
+
+    /// Gets the OAuth2 scopes requested for access tokens.
+    pub fn scopes(&self) -> &[String] {
+        &self.scopes
+    }
+
+    /// Sets a cache consulted before asking the credential for a token.
+    pub fn with_token_cache(mut self, cache: Arc<dyn TokenCache>) -> Self {
+        self.token_cache = Some(cache);
+        self.default_signer = Arc::new(OnceLock::new());
+        self
+    }
+
+    /// Sets the signer used for custom tokens, App Check tokens and signed URLs.
+    pub fn with_signer(mut self, signer: Arc<dyn Signer>) -> Self {
+        self.signer = Some(signer);
+        self
+    }
+
+    /// Gets the signer used for custom tokens, App Check tokens and signed URLs.
+    ///
+    /// Unless one was set with [`AuthMiddleware::with_signer`], this is a
+    /// [`ServiceAccountSigner`] when the credential carries a private key, and an
+    /// [`IamSigner`] for `AppOptions::service_account_id` (or the credential's default
+    /// service account) otherwise. The default signer is created once and shared by clones
+    /// of this middleware, so its parsed key and resolved service account are reused.
+    pub fn signer(&self) -> Result<Arc<dyn Signer>, SignerError> {
+        if let Some(signer) = &self.signer {
+            return Ok(signer.clone());
+        }
+        if let Some(signer) = self.default_signer.get() {
+            return Ok(signer.clone());
+        }
+        let signer: Arc<dyn Signer> = match self.service_account_key() {
+            Some(key) if !key.private_key.is_empty() => {
+                Arc::new(ServiceAccountSigner::from_key(key)?)
+            }
+            _ => Arc::new(IamSigner::from_middleware(self)),
+        };
+        Ok(self.default_signer.get_or_init(|| signer).clone())
+    }
 
-        let scopes = &["https://www.googleapis.com/auth/cloud-platform", "https://www.googleapis.com/auth/firebase"];
+    /// Gets the key source for `url`, calling `create` the first time it is requested.
+    ///
+    /// Key sources cache the keys they fetch, so sharing one between the clients created from
+    /// this middleware (and its clones) downloads the keys once per app instead of once per client.
+    #[cfg(feature = "auth")]
+    pub(crate) fn key_source(
+        &self,
+        url: &'static str,
+        create: impl FnOnce() -> Arc<dyn KeySource>,
+    ) -> Arc<dyn KeySource> {
+        self.key_sources
+            .lock()
+            .unwrap()
+            .entry(url)
+            .or_insert_with(create)
+            .clone()
+    }
 
-        let token = auth.token(scopes).await?;
+    /// Returns a valid access token, from the token cache if it holds a fresh one.
+    pub async fn access_token(&self) -> Result<AccessToken, CredentialError> {
+        let key = self.cache_key();
+        if let Some(cache) = &self.token_cache {
+            if let Some(token) = cache.get(&key).await.filter(AccessToken::is_fresh) {
+                return Ok(token);
+            }
+        }
 
-        Ok(token.token().ok_or_else(|| anyhow::anyhow!("No token found"))?.to_string())
+        let token = self.credential.access_token(&self.scope_refs()).await?;
+        if let Some(cache) = &self.token_cache {
+            cache.put(&key, &token).await;
+        }
+        Ok(token)
     }
+
+    /// Discards any cached token and returns a newly issued one.
+    pub async fn refresh_token(&self) -> Result<AccessToken, CredentialError> {
+        let key = self.cache_key();
+        if let Some(cache) = &self.token_cache {
+            cache.remove(&key).await;
+        }
+
+        let token = self.credential.refresh_token(&self.scope_refs()).await?;
+        if let Some(cache) = &self.token_cache {
+            cache.put(&key, &token).await;
+        }
+        Ok(token)
+    }
+
+    fn scope_refs(&self) -> Vec<&str> {
+        self.scopes.iter().map(String::as_str).collect()
+    }
+
+    /// Identifies the credential and scopes in the token cache.
+    fn cache_key(&self) -> String {
+        let identity = self
+            .credential
+            .service_account_key()
+            .map(|key| key.client_email.clone())
+            .or_else(|| self.credential.project_id())
+            .unwrap_or_else(|| self.project_id.clone());
+        format!("{}|{}", identity, self.scopes.join(" "))
+    }
+
+    /// Fetches (or refreshes) a token and adds the time spent to the request's token latency.
+    async fn timed_token(
+        &self,
+        refresh: bool,
+        extensions: &mut Extensions,
+    ) -> reqwest_middleware::Result<String> {
+        let start = Instant::now();
+        let token = async {
+            if refresh {
+                self.refresh_token().await
+            } else {
+                self.access_token().await
+            }
+        };
+        #[cfg(feature = "tracing")]
+        let token = tracing::Instrument::instrument(
+            token,
+            tracing::debug_span!("firebase.token", refresh),
+        )
+        .await;
+        #[cfg(not(feature = "tracing"))]
+        let token = token.await;
+
+        let elapsed = extensions
+            .get::<TokenLatency>()
+            .map_or(start.elapsed(), |t| t.0 + start.elapsed());
+        extensions.insert(TokenLatency(elapsed));
+
+        token.map(|t| t.token).map_err(|e| {
+            reqwest_middleware::Error::Middleware(anyhow::anyhow!("Failed to get auth token: {}", e))
+        })
+    }
+}
+
+/// Sets the `Authorization: Bearer` header on a request.
+fn set_bearer(req: &mut Request, token: &str) {
+    req.headers_mut().insert(
+        header::AUTHORIZATION,
+        header::HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
+    );
 }
 
 #[async_trait::async_trait]
@@ -85,16 +346,25 @@ impl Middleware for AuthMiddleware {
         extensions: &mut Extensions,
         next: Next<'_>,
     ) -> reqwest_middleware::Result<Response> {
+        if self.emulated {
+            set_bearer(&mut req, EMULATOR_TOKEN);
+            return next.run(req, extensions).await;
+        }
 
-        let token = self.get_token().await.map_err(|e| {
-            reqwest_middleware::Error::Middleware(anyhow::anyhow!("Failed to get auth token: {}", e))
-        })?;
+        let token = self.timed_token(false, extensions).await?;
+        set_bearer(&mut req, &token);
 
-        req.headers_mut().insert(
-            header::AUTHORIZATION,
-            header::HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
-        );
+        // Keep a copy to replay once with a fresh token if the current one is rejected.
+        let retry = req.try_clone();
+        let response = next.clone().run(req, extensions).await?;
 
-        next.run(req, extensions).await
+        match retry {
+            Some(mut retry) if response.status() == StatusCode::UNAUTHORIZED => {
+                let token = self.timed_token(true, extensions).await?;
+                set_bearer(&mut retry, &token);
+                next.run(retry, extensions).await
+            }
+            _ => Ok(response),
+        }
     }
 }
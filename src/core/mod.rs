@@ -1,6 +1,19 @@
+pub mod clock;
+pub mod credential;
+pub mod emulator;
+pub mod error;
+pub mod http;
 pub mod middleware;
+pub mod options;
+pub mod signer;
+pub mod telemetry;
+pub mod token;
+pub mod transport;
 
 use serde::Deserialize;
+use serde_json::Value;
+
+pub use error::FirebaseError;
 
 #[derive(Debug, Deserialize)]
 pub struct FirebaseErrorResponse {
@@ -13,9 +26,10 @@ pub struct FirebaseErrorDetails {
     pub message: String,
     pub status: Option<String>,
     pub errors: Option<Vec<FirebaseSubError>>,
+    pub details: Option<Vec<Value>>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
 pub struct FirebaseSubError {
     pub message: String,
     pub domain: Option<String>,
@@ -28,10 +42,5 @@ impl FirebaseErrorResponse {
     }
 }
 
-pub async fn parse_error_response(response: reqwest::Response, default_msg: &str) -> String {
-    let status = response.status();
-    match response.json::<FirebaseErrorResponse>().await {
-        Ok(error_resp) => error_resp.display_message(),
-        Err(_) => format!("{}: {}", default_msg, status),
-    }
-}
\ No newline at end of file
+#[cfg(test)]
+mod tests;
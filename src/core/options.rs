@@ -0,0 +1,43 @@
+//! Per-app configuration overrides.
+
+/// Options that override values otherwise derived from the app's credential.
+///
+/// All service clients created from an app honour these overrides.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AppOptions {
+    /// The Google Cloud project ID. Defaults to the credential's project ID.
+    pub project_id: Option<String>,
+    /// The default Cloud Storage bucket. Defaults to `{project_id}.appspot.com`.
+    pub storage_bucket: Option<String>,
+    /// The Firestore database ID. Defaults to `(default)`.
+    pub database_id: Option<String>,
+    /// The service account email used to sign tokens and URLs when the credential
+    /// carries no private key.
+    pub service_account_id: Option<String>,
+}
+
+impl AppOptions {
+    /// Sets the project ID override.
+    pub fn with_project_id(mut self, project_id: impl Into<String>) -> Self {
+        self.project_id = Some(project_id.into());
+        self
+    }
+
+    /// Sets the default Cloud Storage bucket.
+    pub fn with_storage_bucket(mut self, bucket: impl Into<String>) -> Self {
+        self.storage_bucket = Some(bucket.into());
+        self
+    }
+
+    /// Sets the Firestore database ID.
+    pub fn with_database_id(mut self, database_id: impl Into<String>) -> Self {
+        self.database_id = Some(database_id.into());
+        self
+    }
+
+    /// Sets the service account email used for signing.
+    pub fn with_service_account_id(mut self, service_account_id: impl Into<String>) -> Self {
+        self.service_account_id = Some(service_account_id.into());
+        self
+    }
+}
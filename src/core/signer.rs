@@ -0,0 +1,271 @@
+//! Signing of custom tokens, App Check tokens and signed URLs.
+//!
+//! A [`Signer`] produces RSA SHA-256 signatures on behalf of a service account. When the
+//! app's credential carries a private key, [`ServiceAccountSigner`] signs locally. Otherwise
+//! (e.g., on Cloud Run with metadata server credentials), [`IamSigner`] asks the IAM
+//! Credentials API to sign with the service account's Google-managed key.
+//!
+//! The signer is chosen automatically, or set explicitly with
+//! [`FirebaseAppBuilder::signer`](crate::FirebaseAppBuilder::signer):
+//!
+//! ```rust,no_run
+//! use firebase_admin_sdk::core::credential::MetadataServerCredential;
+//! use firebase_admin_sdk::core::signer::IamSigner;
+//! use firebase_admin_sdk::FirebaseApp;
+//!
+//! let signer = IamSigner::new(MetadataServerCredential::new())
+//!     .with_service_account_id("my-sa@my-project.iam.gserviceaccount.com");
+//! let app = FirebaseApp::builder()
+//!     .credential(MetadataServerCredential::new())
+//!     .signer(signer)
+//!     .build()
+//!     .unwrap();
+//! ```
+
+use crate::core::credential::{fetch_metadata_value, metadata_client, Credential};
+use crate::core::http::build_client;
+use crate::core::middleware::AuthMiddleware;
+use crate::core::telemetry::Operation;
+use crate::core::transport::TransportMiddleware;
+use crate::core::FirebaseError;
+use async_trait::async_trait;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use reqwest::header;
+use reqwest_middleware::ClientWithMiddleware;
+use rsa::pkcs1::DecodeRsaPrivateKey;
+use rsa::pkcs8::DecodePrivateKey;
+use rsa::{Pkcs1v15Sign, RsaPrivateKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use thiserror::Error;
+use tokio::sync::OnceCell;
+use yup_oauth2::ServiceAccountKey;
+
+const IAM_CREDENTIALS_API: &str = "https://iamcredentials.googleapis.com/v1";
+/// The metadata server endpoint that returns the email of the default service account.
+const METADATA_EMAIL_URL: &str =
+    "http://metadata.google.internal/computeMetadata/v1/instance/service-accounts/default/email";
+
+/// Errors that can occur while signing.
+#[derive(Error, Debug)]
+pub enum SignerError {
+    /// The private key could not be parsed.
+    #[error("Invalid private key: {0}")]
+    InvalidKey(String),
+    /// The signature could not be computed.
+    #[error("Signing failed: {0}")]
+    SigningFailed(String),
+    /// The service account email could not be determined.
+    #[error("Unable to determine the service account email; set AppOptions::service_account_id")]
+    ServiceAccountIdRequired,
+    /// Wrapper for `reqwest::Error`.
+    #[error("HTTP Request failed: {0}")]
+    RequestError(#[from] reqwest::Error),
+    /// Wrapper for `reqwest_middleware::Error`.
+    #[error("Middleware error: {0}")]
+    MiddlewareError(#[from] reqwest_middleware::Error),
+    /// Errors returned by the IAM Credentials API.
+    #[error("API error: {0}")]
+    ApiError(FirebaseError),
+    /// Wrapper for `serde_json::Error`.
+    #[error("Serialization error: {0}")]
+    SerializationError(#[from] serde_json::Error),
+    /// The API returned a malformed signature.
+    #[error("Invalid signature encoding: {0}")]
+    InvalidSignature(#[from] base64::DecodeError),
+}
+
+/// Signs data with RSA SHA-256 (RSASSA-PKCS1-v1_5) on behalf of a service account.
+#[async_trait]
+pub trait Signer: Send + Sync {
+    /// Returns the email of the service account whose key produces the signatures.
+    async fn account_email(&self) -> Result<String, SignerError>;
+
+    /// Signs `data`, returning the raw signature bytes.
+    async fn sign(&self, data: &[u8]) -> Result<Vec<u8>, SignerError>;
+}
+
+#[async_trait]
+impl<T: Signer + ?Sized> Signer for Arc<T> {
+    async fn account_email(&self) -> Result<String, SignerError> {
+        (**self).account_email().await
+    }
+
+    async fn sign(&self, data: &[u8]) -> Result<Vec<u8>, SignerError> {
+        (**self).sign(data).await
+    }
+}
+
+/// Signs locally with a service account private key.
+pub struct ServiceAccountSigner {
+    client_email: String,
+    private_key: RsaPrivateKey,
+}
+
+impl ServiceAccountSigner {
+    /// Creates a signer from a service account email and a PEM encoded (PKCS#1 or PKCS#8)
+    /// RSA private key.
+    pub fn new(client_email: impl Into<String>, private_key_pem: &str) -> Result<Self, SignerError> {
+        let private_key = if private_key_pem.contains("BEGIN RSA PRIVATE KEY") {
+            RsaPrivateKey::from_pkcs1_pem(private_key_pem)
+                .map_err(|e| SignerError::InvalidKey(format!("PKCS1: {}", e)))?
+        } else {
+            RsaPrivateKey::from_pkcs8_pem(private_key_pem)
+                .map_err(|e| SignerError::InvalidKey(format!("PKCS8: {}", e)))?
+        };
+
+        Ok(Self {
+            client_email: client_email.into(),
+            private_key,
+        })
+    }
+
+    /// Creates a signer from a service account key.
+    pub fn from_key(key: &ServiceAccountKey) -> Result<Self, SignerError> {
+        Self::new(key.client_email.clone(), &key.private_key)
+    }
+}
+
+#[async_trait]
+impl Signer for ServiceAccountSigner {
+    async fn account_email(&self) -> Result<String, SignerError> {
+        Ok(self.client_email.clone())
+    }
+
+    async fn sign(&self, data: &[u8]) -> Result<Vec<u8>, SignerError> {
+        let digest = Sha256::digest(data);
+        self.private_key
+            .sign(Pkcs1v15Sign::new::<Sha256>(), &digest)
+            .map_err(|e| SignerError::SigningFailed(e.to_string()))
+    }
+}
+
+#[derive(Serialize)]
+struct SignBlobRequest {
+    payload: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SignBlobResponse {
+    signed_blob: String,
+}
+
+/// Signs with the IAM Credentials `signBlob` API, so no private key is needed locally.
+///
+/// The app's credential needs the `iam.serviceAccounts.signBlob` permission on the signing
+/// service account (e.g., the "Service Account Token Creator" role).
+pub struct IamSigner {
+    client: ClientWithMiddleware,
+    base_url: String,
+    credential: Arc<dyn Credential>,
+    service_account_id: Option<String>,
+    metadata_client: ClientWithMiddleware,
+    email: OnceCell<String>,
+}
+
+impl IamSigner {
+    /// Creates a signer that authenticates `signBlob` calls with the given credential.
+    ///
+    /// The service account defaults to the credential's own service account, or to the
+    /// default service account of the metadata server.
+    pub fn new(credential: impl Credential + 'static) -> Self {
+        Self::from_middleware(&AuthMiddleware::from_credential(Arc::new(credential)))
+    }
+
+    /// Creates a signer that sends `signBlob` calls through the middleware's HTTP stack, for
+    /// the service account set in `AppOptions::service_account_id`.
+    pub(crate) fn from_middleware(middleware: &AuthMiddleware) -> Self {
+        // The metadata server lookup is unauthenticated but still honors the app's transport.
+        let mut metadata = reqwest_middleware::ClientBuilder::new(metadata_client());
+        if let Some(transport) = &middleware.http_config().transport {
+            metadata = metadata.with(TransportMiddleware(transport.clone()));
+        }
+        Self {
+            client: build_client(middleware, "iam"),
+            base_url: IAM_CREDENTIALS_API.to_string(),
+            credential: middleware.credential().clone(),
+            service_account_id: middleware.options().service_account_id.clone(),
+            metadata_client: metadata.build(),
+            email: OnceCell::new(),
+        }
+    }
+
+    /// Sets the email of the service account that signs.
+    pub fn with_service_account_id(mut self, service_account_id: impl Into<String>) -> Self {
+        self.service_account_id = Some(service_account_id.into());
+        self
+    }
+
+    /// Sets the base URL of the IAM Credentials API (e.g., `http://localhost:8080/v1`).
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    async fn resolve_email(&self) -> Result<String, SignerError> {
+        if let Some(id) = &self.service_account_id {
+            return Ok(id.clone());
+        }
+        if let Some(key) = self.credential.service_account_key() {
+            return Ok(key.client_email.clone());
+        }
+
+        fetch_metadata_value(&self.metadata_client, METADATA_EMAIL_URL)
+            .await?
+            .ok_or(SignerError::ServiceAccountIdRequired)
+    }
+}
+
+#[async_trait]
+impl Signer for IamSigner {
+    async fn account_email(&self) -> Result<String, SignerError> {
+        self.email
+            .get_or_try_init(|| self.resolve_email())
+            .await
+            .cloned()
+    }
+
+    async fn sign(&self, data: &[u8]) -> Result<Vec<u8>, SignerError> {
+        let email = self.account_email().await?;
+        let url = format!(
+            "{}/projects/-/serviceAccounts/{}:signBlob",
+            self.base_url, email
+        );
+        let request = SignBlobRequest {
+            payload: STANDARD.encode(data),
+        };
+
+        let response = self
+            .client
+            .post(&url)
+            .with_extension(Operation("sign_blob"))
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(serde_json::to_vec(&request)?)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(SignerError::ApiError(
+                FirebaseError::from_response(response, "Sign blob failed").await,
+            ));
+        }
+
+        let result: SignBlobResponse = response.json().await?;
+        Ok(STANDARD.decode(result.signed_blob)?)
+    }
+}
+
+/// Builds an RS256 JWT whose signature is produced by `signer`.
+#[cfg(any(feature = "auth", feature = "app_check"))]
+pub(crate) async fn sign_jwt(signer: &dyn Signer, claims: &impl Serialize) -> Result<String, SignerError> {
+    use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+
+    let header = URL_SAFE_NO_PAD.encode(br#"{"alg":"RS256","typ":"JWT"}"#);
+    let payload = URL_SAFE_NO_PAD.encode(serde_json::to_vec(claims)?);
+    let signing_input = format!("{}.{}", header, payload);
+    let signature = signer.sign(signing_input.as_bytes()).await?;
+    Ok(format!("{}.{}", signing_input, URL_SAFE_NO_PAD.encode(signature)))
+}
@@ -0,0 +1,174 @@
+//! Tracing spans and metrics for outgoing API calls.
+//!
+//! Every service client wraps its requests in a [`TelemetryMiddleware`] that measures the
+//! total latency (including retries), the number of retries and the time spent acquiring
+//! OAuth2 tokens. With the `tracing` feature enabled each call is recorded as a
+//! `firebase.request` span; a [`MetricsRecorder`] set on the app receives the same data.
+
+use reqwest::{Request, Response};
+use reqwest_middleware::{Middleware, Next};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// The name of the SDK operation a request belongs to (e.g., `get_user`, `commit`).
+///
+/// Service clients attach this to each request as an extension so that middleware layers
+/// can read it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Operation(pub &'static str);
+
+/// Metrics describing a single API call, including all of its retries.
+#[derive(Debug, Clone)]
+pub struct CallMetrics {
+    /// The service that made the call (e.g., `auth`, `firestore`).
+    pub service: &'static str,
+    /// The operation name (e.g., `get_user`).
+    pub operation: &'static str,
+    /// The project the call was made for.
+    pub project_id: String,
+    /// The HTTP status of the final response, if one was received.
+    pub http_status: Option<u16>,
+    /// The number of retries performed after the first attempt.
+    pub retries: u32,
+    /// The total latency of the call, including retries and token acquisition.
+    pub latency: Duration,
+    /// The time spent acquiring access tokens.
+    pub token_latency: Duration,
+}
+
+impl CallMetrics {
+    /// Returns `true` if the call failed, either with a transport error or an error status.
+    pub fn is_error(&self) -> bool {
+        !matches!(self.http_status, Some(status) if status < 400)
+    }
+}
+
+/// Receives metrics for every API call made by the app's service clients.
+///
+/// Implementations are called synchronously on the request path and should be cheap,
+/// e.g. incrementing counters keyed by `service` and `operation`.
+pub trait MetricsRecorder: Send + Sync {
+    /// Records a completed call.
+    fn record_call(&self, metrics: &CallMetrics);
+}
+
+/// Number of attempts made for a request, shared between the telemetry and attempt layers.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Attempts(pub(crate) u32);
+
+/// Time spent acquiring access tokens for a request.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct TokenLatency(pub(crate) Duration);
+
+/// Outermost layer of every service client; records spans and metrics.
+pub(crate) struct TelemetryMiddleware {
+    service: &'static str,
+    project_id: String,
+    recorder: Option<Arc<dyn MetricsRecorder>>,
+}
+
+impl TelemetryMiddleware {
+    pub(crate) fn new(
+        service: &'static str,
+        project_id: String,
+        recorder: Option<Arc<dyn MetricsRecorder>>,
+    ) -> Self {
+        Self {
+            service,
+            project_id,
+            recorder,
+        }
+    }
+}
+
+/// Falls back to the lowercase HTTP method for requests that were not tagged with an [`Operation`].
+fn default_operation(req: &Request) -> &'static str {
+    match req.method().as_str() {
+        "GET" => "get",
+        "POST" => "post",
+        "PUT" => "put",
+        "PATCH" => "patch",
+        "DELETE" => "delete",
+        _ => "request",
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for TelemetryMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut http::Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        let operation = extensions
+            .get::<Operation>()
+            .map(|op| op.0)
+            .unwrap_or_else(|| default_operation(&req));
+        extensions.insert(Attempts::default());
+        extensions.insert(TokenLatency::default());
+
+        #[cfg(feature = "tracing")]
+        let span = tracing::info_span!(
+            "firebase.request",
+            service = self.service,
+            operation,
+            project = %self.project_id,
+            http.status = tracing::field::Empty,
+            retries = tracing::field::Empty,
+            latency_ms = tracing::field::Empty,
+            token_latency_ms = tracing::field::Empty,
+        );
+
+        let start = Instant::now();
+        #[cfg(feature = "tracing")]
+        let result = tracing::Instrument::instrument(next.run(req, extensions), span.clone()).await;
+        #[cfg(not(feature = "tracing"))]
+        let result = next.run(req, extensions).await;
+
+        let metrics = CallMetrics {
+            service: self.service,
+            operation,
+            project_id: self.project_id.clone(),
+            http_status: result.as_ref().ok().map(|r| r.status().as_u16()),
+            retries: extensions
+                .get::<Attempts>()
+                .map_or(0, |a| a.0.saturating_sub(1)),
+            latency: start.elapsed(),
+            token_latency: extensions.get::<TokenLatency>().map_or(Duration::ZERO, |t| t.0),
+        };
+
+        #[cfg(feature = "tracing")]
+        {
+            if let Some(status) = metrics.http_status {
+                span.record("http.status", status);
+            }
+            span.record("retries", metrics.retries);
+            span.record("latency_ms", metrics.latency.as_millis() as u64);
+            span.record("token_latency_ms", metrics.token_latency.as_millis() as u64);
+        }
+
+        if let Some(recorder) = &self.recorder {
+            recorder.record_call(&metrics);
+        }
+
+        result
+    }
+}
+
+/// Counts attempts; sits directly inside the retry layer so it runs once per attempt.
+pub(crate) struct AttemptMiddleware;
+
+#[async_trait::async_trait]
+impl Middleware for AttemptMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut http::Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        let attempts = extensions.get::<Attempts>().copied().unwrap_or_default();
+        extensions.insert(Attempts(attempts.0 + 1));
+        next.run(req, extensions).await
+    }
+}
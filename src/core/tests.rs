@@ -0,0 +1,724 @@
+use super::credential::{
+    ApplicationDefaultCredential, Credential, CredentialError, MetadataServerCredential,
+    StaticTokenCredential,
+};
+use super::error::FirebaseError;
+use super::middleware::AuthMiddleware;
+use httpmock::prelude::*;
+use reqwest::Client;
+use reqwest_middleware::ClientBuilder;
+use serde_json::json;
+use std::sync::Arc;
+
+#[tokio::test]
+async fn test_static_token_credential() {
+    let server = MockServer::start();
+    let credential = StaticTokenCredential::new("static-token").with_project_id("test-project");
+    let middleware = AuthMiddleware::from_credential(Arc::new(credential));
+    assert_eq!(middleware.project_id(), "test-project");
+    assert!(middleware.service_account_key().is_none());
+
+    let client = ClientBuilder::new(Client::new()).with(middleware).build();
+
+    let mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/resource")
+            .header("authorization", "Bearer static-token");
+        then.status(200);
+    });
+
+    client.get(server.url("/resource")).send().await.unwrap();
+
+    mock.assert();
+}
+
+#[tokio::test]
+async fn test_metadata_server_credential_project_id() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/project-id")
+            .header("metadata-flavor", "Google");
+        then.status(200).body("metadata-project\n");
+    });
+
+    let credential = Arc::new(
+        MetadataServerCredential::with_token_url(server.url("/token"))
+            .with_project_id_url(server.url("/project-id")),
+    );
+
+    // Building synchronously never contacts the metadata server.
+    assert_eq!(credential.project_id(), None);
+    let result = crate::FirebaseApp::builder().credential(credential.clone()).build();
+    assert!(matches!(result, Err(crate::builder::BuildError::ProjectIdRequired)));
+    mock.assert_calls(0);
+
+    let app = crate::FirebaseApp::builder()
+        .credential(credential.clone())
+        .build_async()
+        .await
+        .unwrap();
+    assert_eq!(app.middleware.project_id(), "metadata-project");
+    assert_eq!(credential.project_id().as_deref(), Some("metadata-project"));
+    crate::FirebaseApp::builder()
+        .credential(credential.clone())
+        .build_async()
+        .await
+        .unwrap();
+    mock.assert_calls(1);
+}
+
+#[test]
+fn test_builder_requires_project_id() {
+    let result = crate::FirebaseApp::builder()
+        .credential(StaticTokenCredential::new("static-token"))
+        .build();
+    assert!(matches!(result, Err(crate::builder::BuildError::ProjectIdRequired)));
+    let result = crate::FirebaseApp::from_credential(StaticTokenCredential::new("static-token"));
+    assert!(matches!(result, Err(crate::builder::BuildError::ProjectIdRequired)));
+
+    let options = super::options::AppOptions {
+        project_id: Some("override-project".to_string()),
+        ..Default::default()
+    };
+    let app = crate::FirebaseApp::builder()
+        .credential(StaticTokenCredential::new("static-token"))
+        .options(options)
+        .build()
+        .unwrap();
+    assert_eq!(app.middleware.project_id(), "override-project");
+}
+
+#[tokio::test]
+async fn test_metadata_server_credential_shares_concurrent_refresh() {
+    let server = MockServer::start();
+    let credential = Arc::new(MetadataServerCredential::with_token_url(server.url("/token")));
+
+    let mock = server.mock(|when, then| {
+        when.method(GET).path("/token");
+        then.status(200)
+            .header("content-type", "application/json")
+            .delay(std::time::Duration::from_millis(50))
+            .json_body(json!({ "access_token": "metadata-token", "expires_in": 3599 }));
+    });
+
+    let requests: Vec<_> = (0..8)
+        .map(|_| {
+            let credential = credential.clone();
+            tokio::spawn(async move { credential.access_token(&["scope"]).await.unwrap() })
+        })
+        .collect();
+    for request in requests {
+        assert_eq!(request.await.unwrap().token, "metadata-token");
+    }
+    mock.assert_calls(1);
+
+    // Only an explicit refresh bypasses the cached token.
+    credential.refresh_token(&["scope"]).await.unwrap();
+    mock.assert_calls(2);
+}
+
+#[tokio::test]
+async fn test_metadata_server_credential_caches_token() {
+    let server = MockServer::start();
+    let credential = MetadataServerCredential::with_token_url(server.url("/token"));
+
+    let mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/token")
+            .header("metadata-flavor", "Google")
+            .query_param("scopes", "scope-a,scope-b");
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "access_token": "metadata-token",
+                "expires_in": 3599,
+                "token_type": "Bearer"
+            }));
+    });
+
+    let first = credential.access_token(&["scope-a", "scope-b"]).await.unwrap();
+    let second = credential.access_token(&["scope-a", "scope-b"]).await.unwrap();
+
+    assert_eq!(first.token, "metadata-token");
+    assert!(first.expires_at.is_some());
+    assert_eq!(first, second);
+
+    mock.assert_calls(1);
+}
+
+#[test]
+fn test_application_default_credential_file_types() {
+    let authorized_user = json!({
+        "type": "authorized_user",
+        "client_id": "client-id",
+        "client_secret": "client-secret",
+        "refresh_token": "refresh-token",
+        "quota_project_id": "quota-project"
+    });
+    let credential =
+        ApplicationDefaultCredential::from_json(authorized_user.to_string().as_bytes()).unwrap();
+    assert_eq!(credential.project_id().as_deref(), Some("quota-project"));
+    assert!(credential.service_account_key().is_none());
+
+    let unknown = json!({ "type": "impersonated_service_account" });
+    let result = ApplicationDefaultCredential::from_json(unknown.to_string().as_bytes());
+    assert!(matches!(result, Err(CredentialError::UnsupportedType(t)) if t == "impersonated_service_account"));
+}
+
+struct HeaderLayer;
+
+#[async_trait::async_trait]
+impl reqwest_middleware::Middleware for HeaderLayer {
+    async fn handle(
+        &self,
+        mut req: reqwest::Request,
+        extensions: &mut http::Extensions,
+        next: reqwest_middleware::Next<'_>,
+    ) -> reqwest_middleware::Result<reqwest::Response> {
+        req.headers_mut()
+            .insert("x-layer", reqwest::header::HeaderValue::from_static("applied"));
+        next.run(req, extensions).await
+    }
+}
+
+#[tokio::test]
+async fn test_builder_http_settings() {
+    let server = MockServer::start();
+    let app = crate::FirebaseApp::builder()
+        .credential(StaticTokenCredential::new("static-token").with_project_id("test-project"))
+        .user_agent("custom-agent/1.0")
+        .timeout(std::time::Duration::from_secs(10))
+        .max_retries(0)
+        .with_middleware(HeaderLayer)
+        .emulators(super::emulator::EmulatorConfig::default())
+        .build()
+        .unwrap();
+
+    let client = super::http::build_client(&app.middleware, "test");
+
+    let mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/resource")
+            .header("user-agent", "custom-agent/1.0")
+            .header("x-layer", "applied")
+            .header("authorization", "Bearer static-token");
+        then.status(503);
+    });
+
+    let response = client.get(server.url("/resource")).send().await.unwrap();
+    assert_eq!(response.status(), 503);
+
+    // With retries disabled, the transient failure is not retried.
+    mock.assert_calls(1);
+}
+
+#[test]
+fn test_app_registry() {
+    let app = crate::FirebaseApp::from_credential(
+        StaticTokenCredential::new("token").with_project_id("project-a"),
+    )
+    .unwrap();
+
+    let registered = crate::FirebaseApp::initialize_named("registry-test", app.clone()).unwrap();
+    assert_eq!(registered.name(), "registry-test");
+    assert_eq!(app.name(), crate::DEFAULT_APP_NAME);
+
+    let duplicate = crate::FirebaseApp::initialize_named("registry-test", app);
+    assert!(matches!(duplicate, Err(crate::AppError::DuplicateApp(name)) if name == "registry-test"));
+
+    let fetched = crate::FirebaseApp::get_app("registry-test").unwrap();
+    assert_eq!(fetched.project_id(), "project-a");
+
+    crate::FirebaseApp::delete_app("registry-test").unwrap();
+    assert!(matches!(
+        crate::FirebaseApp::get_app("registry-test"),
+        Err(crate::AppError::NoApp(_))
+    ));
+    assert!(crate::FirebaseApp::delete_app("registry-test").is_err());
+}
+
+#[cfg(all(feature = "storage", feature = "firestore"))]
+#[test]
+fn test_app_options_overrides() {
+    let app = crate::FirebaseApp::from_credential(
+        StaticTokenCredential::new("token").with_project_id("credential-project"),
+    )
+    .unwrap()
+    .with_emulators(super::emulator::EmulatorConfig::default())
+    .with_options(
+        super::options::AppOptions::default()
+            .with_project_id("override-project")
+            .with_storage_bucket("custom-bucket")
+            .with_database_id("analytics"),
+    );
+
+    assert_eq!(app.project_id(), "override-project");
+    assert_eq!(app.storage().bucket(None).name(), "custom-bucket");
+    assert!(app
+        .firestore()
+        .collection("users")
+        .path
+        .starts_with(
+            "https://firestore.googleapis.com/v1/projects/override-project/databases/analytics/documents"
+        ));
+}
+
+#[test]
+fn test_firebase_error_parsing() {
+    let auth = FirebaseError::from_body(
+        400,
+        r#"{"error":{"code":400,"message":"WEAK_PASSWORD : Password should be at least 6 characters"}}"#,
+        "Create user failed",
+    );
+    assert_eq!(auth.status, "INVALID_ARGUMENT");
+    assert_eq!(auth.code.as_deref(), Some("WEAK_PASSWORD"));
+    assert!(!auth.is_retryable());
+
+    let quota = FirebaseError::from_body(
+        429,
+        r#"{"error":{"code":429,"message":"Quota exceeded.","status":"RESOURCE_EXHAUSTED","details":[{"@type":"type.googleapis.com/google.rpc.ErrorInfo","reason":"QUOTA_EXCEEDED"}]}}"#,
+        "Request failed",
+    );
+    assert_eq!(quota.status, "RESOURCE_EXHAUSTED");
+    assert!(quota.has_code("QUOTA_EXCEEDED"));
+    assert!(quota.is_retryable());
+
+    let storage = FirebaseError::from_body(
+        404,
+        r#"{"error":{"code":404,"message":"No such object: bucket/file","errors":[{"message":"No such object: bucket/file","domain":"global","reason":"notFound"}]}}"#,
+        "Download failed",
+    );
+    assert_eq!(storage.status, "NOT_FOUND");
+    assert_eq!(storage.code, None);
+    assert_eq!(storage.sub_errors[0].reason.as_deref(), Some("notFound"));
+
+    let unstructured = FirebaseError::from_body(503, "Service Unavailable", "Upload failed");
+    assert_eq!(unstructured.status, "UNAVAILABLE");
+    assert_eq!(unstructured.message, "Upload failed: Service Unavailable");
+    assert_eq!(unstructured.to_string(), "Upload failed: Service Unavailable (code: 503)");
+    assert!(unstructured.is_retryable());
+}
+
+#[derive(Default)]
+struct RecordingMetrics {
+    calls: std::sync::Mutex<Vec<super::telemetry::CallMetrics>>,
+}
+
+impl super::telemetry::MetricsRecorder for Arc<RecordingMetrics> {
+    fn record_call(&self, metrics: &super::telemetry::CallMetrics) {
+        self.calls.lock().unwrap().push(metrics.clone());
+    }
+}
+
+#[tokio::test]
+async fn test_metrics_recorder() {
+    let server = MockServer::start();
+    let recorder = Arc::new(RecordingMetrics::default());
+    let app = crate::FirebaseApp::builder()
+        .credential(StaticTokenCredential::new("static-token").with_project_id("test-project"))
+        .retry_policy(
+            reqwest_retry::policies::ExponentialBackoff::builder()
+                .retry_bounds(
+                    std::time::Duration::from_millis(1),
+                    std::time::Duration::from_millis(2),
+                )
+                .build_with_max_retries(2),
+        )
+        .metrics_recorder(recorder.clone())
+        .emulators(super::emulator::EmulatorConfig::default())
+        .build()
+        .unwrap();
+
+    let client = super::http::build_client(&app.middleware, "test");
+
+    let mock = server.mock(|when, then| {
+        when.method(GET).path("/resource");
+        then.status(503);
+    });
+
+    client
+        .get(server.url("/resource"))
+        .with_extension(super::telemetry::Operation("probe"))
+        .send()
+        .await
+        .unwrap();
+    mock.assert_calls(3);
+
+    let calls = recorder.calls.lock().unwrap();
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].service, "test");
+    assert_eq!(calls[0].operation, "probe");
+    assert_eq!(calls[0].project_id, "test-project");
+    assert_eq!(calls[0].http_status, Some(503));
+    assert_eq!(calls[0].retries, 2);
+    assert!(calls[0].is_error());
+}
+
+#[cfg(feature = "auth")]
+#[tokio::test]
+async fn test_email_link_operations() {
+    let server = MockServer::start();
+    let recorder = Arc::new(RecordingMetrics::default());
+    let app = crate::FirebaseApp::builder()
+        .credential(StaticTokenCredential::new("static-token").with_project_id("test-project"))
+        .metrics_recorder(recorder.clone())
+        .emulators(super::emulator::EmulatorConfig::default().with_auth_host(server.address().to_string()))
+        .build()
+        .unwrap();
+
+    server.mock(|when, then| {
+        when.method(POST).path_includes("/accounts:sendOobCode");
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({ "email": "user@example.com", "oobLink": "https://example.com/link" }));
+    });
+
+    let auth = app.auth();
+    auth.generate_password_reset_link("user@example.com", None).await.unwrap();
+    auth.send_password_reset_email("user@example.com", None).await.unwrap();
+
+    let calls = recorder.calls.lock().unwrap();
+    let operations: Vec<_> = calls.iter().map(|call| call.operation).collect();
+    assert_eq!(operations, ["generate_email_link", "send_email_link"]);
+}
+
+#[cfg(feature = "auth")]
+#[tokio::test]
+async fn test_user_lookup_operations() {
+    use crate::auth::models::UserIdentifier;
+
+    let server = MockServer::start();
+    let recorder = Arc::new(RecordingMetrics::default());
+    let app = crate::FirebaseApp::builder()
+        .credential(StaticTokenCredential::new("static-token").with_project_id("test-project"))
+        .metrics_recorder(recorder.clone())
+        .emulators(super::emulator::EmulatorConfig::default().with_auth_host(server.address().to_string()))
+        .build()
+        .unwrap();
+
+    server.mock(|when, then| {
+        when.method(POST).path_includes("/accounts:lookup");
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({ "users": [{ "localId": "uid-1", "emailVerified": false, "disabled": false }] }));
+    });
+
+    let auth = app.auth();
+    auth.get_user("uid-1").await.unwrap();
+    auth.get_users(&[UserIdentifier::Uid("uid-1".to_string())]).await.unwrap();
+
+    let calls = recorder.calls.lock().unwrap();
+    let operations: Vec<_> = calls.iter().map(|call| call.operation).collect();
+    assert_eq!(operations, ["get_user", "get_users"]);
+}
+
+/// Issues `token-N` tokens, counting how often it is asked and which scopes were requested.
+#[derive(Default)]
+struct CountingCredential {
+    issued: std::sync::atomic::AtomicUsize,
+    scopes: std::sync::Mutex<Vec<String>>,
+}
+
+#[async_trait::async_trait]
+impl Credential for CountingCredential {
+    async fn access_token(
+        &self,
+        scopes: &[&str],
+    ) -> Result<super::credential::AccessToken, CredentialError> {
+        *self.scopes.lock().unwrap() = scopes.iter().map(|s| s.to_string()).collect();
+        let n = self.issued.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+        Ok(super::credential::AccessToken {
+            token: format!("token-{}", n),
+            expires_at: Some(std::time::SystemTime::now() + std::time::Duration::from_secs(3600)),
+        })
+    }
+
+    fn project_id(&self) -> Option<String> {
+        Some("test-project".to_string())
+    }
+}
+
+#[tokio::test]
+async fn test_reauthenticates_once_on_unauthorized() {
+    let server = MockServer::start();
+    let credential = Arc::new(CountingCredential::default());
+    let middleware = AuthMiddleware::from_credential(credential.clone());
+    let client = ClientBuilder::new(Client::new()).with(middleware).build();
+
+    let rejected = server.mock(|when, then| {
+        when.method(GET)
+            .path("/resource")
+            .header("authorization", "Bearer token-1");
+        then.status(401);
+    });
+    let accepted = server.mock(|when, then| {
+        when.method(GET)
+            .path("/resource")
+            .header("authorization", "Bearer token-2");
+        then.status(200);
+    });
+
+    let response = client.get(server.url("/resource")).send().await.unwrap();
+    assert_eq!(response.status(), 200);
+    rejected.assert_calls(1);
+    accepted.assert_calls(1);
+    assert_eq!(credential.issued.load(std::sync::atomic::Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_access_token_scopes_and_file_cache() {
+    let dir = std::env::temp_dir().join(format!("firebase-token-cache-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("tokens.json");
+
+    let first = Arc::new(CountingCredential::default());
+    let app = crate::FirebaseApp::builder()
+        .credential(first.clone())
+        .scopes(["https://www.googleapis.com/auth/datastore"])
+        .token_cache(super::token::FileTokenCache::new(&path))
+        .build()
+        .unwrap();
+    app.prewarm().await.unwrap();
+    assert_eq!(
+        *first.scopes.lock().unwrap(),
+        vec!["https://www.googleapis.com/auth/datastore".to_string()]
+    );
+
+    // A second app (e.g., another process) reuses the cached token.
+    let second = Arc::new(CountingCredential::default());
+    let other = crate::FirebaseApp::builder()
+        .credential(second.clone())
+        .scopes(["https://www.googleapis.com/auth/datastore"])
+        .token_cache(super::token::FileTokenCache::new(&path))
+        .build()
+        .unwrap();
+    let token = other.access_token().await.unwrap();
+    assert_eq!(token.token, "token-1");
+    assert!(token.expires_at.is_some());
+    assert_eq!(second.issued.load(std::sync::atomic::Ordering::SeqCst), 0);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_file_token_cache_concurrent_private_writes() {
+    use super::credential::AccessToken;
+    use super::token::{FileTokenCache, TokenCache};
+
+    let dir = std::env::temp_dir().join(format!("firebase-token-cache-writes-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("tokens.json");
+    let cache = FileTokenCache::new(&path);
+
+    let writes: Vec<_> = (0..16)
+        .map(|i| {
+            let cache = cache.clone();
+            tokio::spawn(async move {
+                let token = AccessToken {
+                    token: format!("token-{}", i),
+                    expires_at: None,
+                };
+                cache.put(&format!("key-{}", i), &token).await;
+            })
+        })
+        .collect();
+    for write in writes {
+        write.await.unwrap();
+    }
+
+    // Every write replaced the file with a complete cache, and no temporary files remain.
+    let contents: serde_json::Value = serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+    assert!(contents.as_object().is_some_and(|tokens| !tokens.is_empty()));
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+    assert!(cache.get("missing").await.is_none());
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(feature = "auth")]
+/// Answers every request from memory, recording the URL and `Authorization` header it saw.
+#[derive(Default)]
+struct InMemoryTransport {
+    requests: std::sync::Mutex<Vec<(String, Option<String>)>>,
+}
+
+#[cfg(feature = "auth")]
+#[async_trait::async_trait]
+impl super::transport::HttpTransport for InMemoryTransport {
+    async fn execute(&self, request: reqwest::Request) -> reqwest_middleware::Result<reqwest::Response> {
+        let auth = request
+            .headers()
+            .get("authorization")
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        self.requests
+            .lock()
+            .unwrap()
+            .push((request.url().to_string(), auth));
+
+        let body = json!({ "users": [{ "localId": "uid-1", "email": "user@example.com", "emailVerified": false, "disabled": false }] });
+        let response = http::Response::builder()
+            .status(200)
+            .header("content-type", "application/json")
+            .body(body.to_string())
+            .unwrap();
+        Ok(reqwest::Response::from(response))
+    }
+}
+
+#[cfg(feature = "auth")]
+#[tokio::test]
+async fn test_custom_transport() {
+    let transport = Arc::new(InMemoryTransport::default());
+    let app = crate::FirebaseApp::builder()
+        .credential(StaticTokenCredential::new("static-token").with_project_id("test-project"))
+        .transport(transport.clone())
+        .emulators(super::emulator::EmulatorConfig::default())
+        .build()
+        .unwrap();
+
+    let user = app.auth().get_user("uid-1").await.unwrap();
+    assert_eq!(user.local_id, "uid-1");
+
+    let requests = transport.requests.lock().unwrap();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(
+        requests[0].0,
+        "https://identitytoolkit.googleapis.com/v1/projects/test-project/accounts:lookup"
+    );
+    assert_eq!(requests[0].1.as_deref(), Some("Bearer static-token"));
+}
+
+#[cfg(feature = "auth")]
+#[tokio::test]
+async fn test_token_keys_fetched_through_transport() {
+    use base64::Engine;
+
+    let transport = Arc::new(InMemoryTransport::default());
+    let app = crate::FirebaseApp::builder()
+        .credential(StaticTokenCredential::new("static-token").with_project_id("test-project"))
+        .transport(transport.clone())
+        .emulators(super::emulator::EmulatorConfig::default())
+        .build()
+        .unwrap();
+
+    let b64 = |value: serde_json::Value| {
+        base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(value.to_string())
+    };
+    let token = format!(
+        "{}.{}.c2ln",
+        b64(json!({ "alg": "RS256", "kid": "kid-1" })),
+        b64(json!({ "sub": "uid-1" }))
+    );
+    // The transport doesn't serve certificates, so verification fails after the fetch.
+    assert!(app.auth().verify_id_token(&token, false).await.is_err());
+
+    let requests = transport.requests.lock().unwrap();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].0, crate::auth::keys::ID_TOKEN_CERTS_URL);
+    assert_eq!(requests[0].1, None);
+}
+
+#[tokio::test]
+async fn test_iam_signer_sign_blob() {
+    use super::signer::{IamSigner, Signer};
+
+    let server = MockServer::start();
+    let signer = IamSigner::new(StaticTokenCredential::new("static-token"))
+        .with_service_account_id("signer@test-project.iam.gserviceaccount.com")
+        .with_base_url(server.url("/v1"));
+
+    let mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/v1/projects/-/serviceAccounts/signer@test-project.iam.gserviceaccount.com:signBlob")
+            .header("authorization", "Bearer static-token")
+            .json_body(json!({ "payload": "aGVsbG8=" }));
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({ "keyId": "key-1", "signedBlob": "AQID" }));
+    });
+
+    assert_eq!(
+        signer.account_email().await.unwrap(),
+        "signer@test-project.iam.gserviceaccount.com"
+    );
+    assert_eq!(signer.sign(b"hello").await.unwrap(), vec![1, 2, 3]);
+    mock.assert();
+}
+
+#[tokio::test]
+async fn test_signer_falls_back_to_iam_without_private_key() {
+    let options = super::options::AppOptions::default()
+        .with_service_account_id("signer@test-project.iam.gserviceaccount.com");
+    let middleware = AuthMiddleware::from_credential(Arc::new(
+        StaticTokenCredential::new("static-token").with_project_id("test-project"),
+    ))
+    .with_options(options);
+
+    let signer = middleware.signer().unwrap();
+    assert_eq!(
+        signer.account_email().await.unwrap(),
+        "signer@test-project.iam.gserviceaccount.com"
+    );
+
+    // The default signer is created once and shared with clones.
+    assert!(Arc::ptr_eq(&signer, &middleware.signer().unwrap()));
+    assert!(Arc::ptr_eq(
+        &signer,
+        &middleware.with_tenant("tenant-a").signer().unwrap()
+    ));
+}
+
+/// Answers metadata server requests with a fixed status and body.
+struct MetadataTransport(u16, &'static str);
+
+#[async_trait::async_trait]
+impl super::transport::HttpTransport for MetadataTransport {
+    async fn execute(&self, request: reqwest::Request) -> reqwest_middleware::Result<reqwest::Response> {
+        assert_eq!(request.headers()["metadata-flavor"], "Google");
+        let response = http::Response::builder()
+            .status(self.0)
+            .body(self.1.to_string())
+            .unwrap();
+        Ok(reqwest::Response::from(response))
+    }
+}
+
+#[tokio::test]
+async fn test_iam_signer_resolves_metadata_email() {
+    use super::signer::SignerError;
+
+    let signer_for = |status, body| {
+        let http = super::http::HttpConfig {
+            transport: Some(Arc::new(MetadataTransport(status, body))),
+            ..Default::default()
+        };
+        AuthMiddleware::from_credential(Arc::new(
+            StaticTokenCredential::new("static-token").with_project_id("test-project"),
+        ))
+        .with_http_config(http)
+        .signer()
+        .unwrap()
+    };
+
+    let email = signer_for(200, "default@test-project.iam.gserviceaccount.com\n")
+        .account_email()
+        .await
+        .unwrap();
+    assert_eq!(email, "default@test-project.iam.gserviceaccount.com");
+
+    // Only a missing metadata server means the service account must be configured.
+    let missing = signer_for(404, "").account_email().await;
+    assert!(matches!(missing, Err(SignerError::ServiceAccountIdRequired)));
+
+    let unavailable = signer_for(503, "").account_email().await;
+    assert!(matches!(unavailable, Err(SignerError::MiddlewareError(_))));
+}
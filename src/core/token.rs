@@ -0,0 +1,177 @@
+//! Access token caching shared across service clients and processes.
+//!
+//! By default each credential caches its own tokens in memory. A [`TokenCache`] set on the
+//! app sits in front of the credential, so that several processes (e.g., workers behind a
+//! load balancer) can reuse a single token instead of each requesting their own.
+
+use crate::core::credential::AccessToken;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::io::AsyncWriteExt;
+
+/// The OAuth2 scopes requested for Firebase API calls unless overridden.
+pub const DEFAULT_SCOPES: [&str; 2] = [
+    "https://www.googleapis.com/auth/cloud-platform",
+    "https://www.googleapis.com/auth/firebase",
+];
+
+/// A store for access tokens, keyed by credential identity and scopes.
+///
+/// Implementations must be safe to call concurrently. Stale tokens may be returned; callers
+/// check [`AccessToken::is_fresh`] before using them.
+#[async_trait]
+pub trait TokenCache: Send + Sync {
+    /// Returns the cached token for `key`, if any.
+    async fn get(&self, key: &str) -> Option<AccessToken>;
+
+    /// Stores `token` under `key`.
+    async fn put(&self, key: &str, token: &AccessToken);
+
+    /// Removes the token stored under `key`.
+    async fn remove(&self, key: &str);
+}
+
+/// A [`TokenCache`] that keeps tokens in process memory.
+///
+/// Useful for sharing one token between several `FirebaseApp` instances that use the same
+/// credential.
+#[derive(Debug, Default)]
+pub struct InMemoryTokenCache {
+    tokens: Mutex<HashMap<String, AccessToken>>,
+}
+
+impl InMemoryTokenCache {
+    /// Creates an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl TokenCache for InMemoryTokenCache {
+    async fn get(&self, key: &str) -> Option<AccessToken> {
+        self.tokens.lock().unwrap().get(key).cloned()
+    }
+
+    async fn put(&self, key: &str, token: &AccessToken) {
+        self.tokens
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), token.clone());
+    }
+
+    async fn remove(&self, key: &str) {
+        self.tokens.lock().unwrap().remove(key);
+    }
+}
+
+/// A [`TokenCache`] that persists tokens in a JSON file, shared by every process that
+/// points at the same path.
+///
+/// Writes go to a temporary file that is then renamed over the cache file, so readers never
+/// observe a partially written cache. On Unix, the file is only readable by its owner.
+/// Concurrent writers may overwrite each other's entries, which only causes an extra token
+/// request. Errors reading or writing the file are ignored.
+#[derive(Debug, Clone)]
+pub struct FileTokenCache {
+    path: PathBuf,
+}
+
+#[derive(Serialize, Deserialize)]
+struct StoredToken {
+    token: String,
+    /// Expiry as seconds since the Unix epoch.
+    expires_at: Option<u64>,
+}
+
+impl FileTokenCache {
+    /// Creates a cache backed by the file at `path`. The file is created on first write.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    async fn read(&self) -> HashMap<String, StoredToken> {
+        match tokio::fs::read(&self.path).await {
+            Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_default(),
+            Err(_) => HashMap::new(),
+        }
+    }
+
+    async fn write(&self, tokens: &HashMap<String, StoredToken>) {
+        let Ok(bytes) = serde_json::to_vec(tokens) else {
+            return;
+        };
+        // Unique per write, so concurrent writers never share a temporary file.
+        let tmp = self.path.with_extension(format!(
+            "tmp.{}.{}",
+            std::process::id(),
+            TMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        if write_private(&tmp, &bytes).await.is_ok() {
+            let _ = tokio::fs::rename(&tmp, &self.path).await;
+        } else {
+            let _ = tokio::fs::remove_file(&tmp).await;
+        }
+    }
+}
+
+/// Distinguishes the temporary files of concurrent writes within a process.
+static TMP_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Creates `path` readable and writable only by the current user (on Unix), and writes
+/// `bytes` to it, since the cache holds bearer tokens.
+async fn write_private(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    let mut options = tokio::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+    let mut file = options.open(path).await?;
+    file.write_all(bytes).await?;
+    file.flush().await
+}
+
+#[async_trait]
+impl TokenCache for FileTokenCache {
+    async fn get(&self, key: &str) -> Option<AccessToken> {
+        let stored = self.read().await.remove(key)?;
+        Some(AccessToken {
+            token: stored.token,
+            expires_at: stored
+                .expires_at
+                .map(|secs| UNIX_EPOCH + Duration::from_secs(secs)),
+        })
+    }
+
+    async fn put(&self, key: &str, token: &AccessToken) {
+        let mut tokens = self.read().await;
+        tokens.insert(
+            key.to_string(),
+            StoredToken {
+                token: token.token.clone(),
+                expires_at: token
+                    .expires_at
+                    .and_then(|exp| exp.duration_since(UNIX_EPOCH).ok())
+                    .map(|d| d.as_secs()),
+            },
+        );
+        // Drop expired entries so the file does not grow without bound.
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        tokens.retain(|_, t| t.expires_at.is_none_or(|exp| exp > now));
+        self.write(&tokens).await;
+    }
+
+    async fn remove(&self, key: &str) {
+        let mut tokens = self.read().await;
+        if tokens.remove(key).is_some() {
+            self.write(&tokens).await;
+        }
+    }
+}
@@ -0,0 +1,66 @@
+//! Pluggable HTTP transport.
+//!
+//! By default requests are sent with `reqwest`. An [`HttpTransport`] set on the app replaces
+//! the network layer for every service client while keeping the SDK's middleware stack
+//! (telemetry, retries, extra layers and authentication), so downstream crates can test
+//! Firebase-using code against recorded or in-memory responses.
+
+use async_trait::async_trait;
+use reqwest::{Request, Response};
+use reqwest_middleware::{Middleware, Next};
+use std::sync::Arc;
+
+/// Sends a fully prepared request and returns its response.
+///
+/// Requests reach the transport after all middleware has run, so they already carry the
+/// `Authorization` header.
+///
+/// # Examples
+///
+/// An in-memory transport that answers every request with an empty JSON object:
+///
+/// ```rust
+/// use firebase_admin_sdk::core::transport::HttpTransport;
+/// use reqwest::{Request, Response};
+///
+/// struct EmptyJson;
+///
+/// #[async_trait::async_trait]
+/// impl HttpTransport for EmptyJson {
+///     async fn execute(&self, _request: Request) -> reqwest_middleware::Result<Response> {
+///         let response = http::Response::builder()
+///             .status(200)
+///             .header("content-type", "application/json")
+///             .body("{}")
+///             .unwrap();
+///         Ok(Response::from(response))
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait HttpTransport: Send + Sync {
+    /// Executes the request.
+    async fn execute(&self, request: Request) -> reqwest_middleware::Result<Response>;
+}
+
+#[async_trait]
+impl<T: HttpTransport + ?Sized> HttpTransport for Arc<T> {
+    async fn execute(&self, request: Request) -> reqwest_middleware::Result<Response> {
+        (**self).execute(request).await
+    }
+}
+
+/// Innermost middleware layer that hands requests to an [`HttpTransport`] instead of `reqwest`.
+pub(crate) struct TransportMiddleware(pub(crate) Arc<dyn HttpTransport>);
+
+#[async_trait]
+impl Middleware for TransportMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        _extensions: &mut http::Extensions,
+        _next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        self.0.execute(req).await
+    }
+}
@@ -16,13 +16,11 @@
 //! # }
 //! ```
 
+use crate::core::http::build_client;
 use crate::core::middleware::AuthMiddleware;
-use reqwest::Client;
-use reqwest::StatusCode;
-use reqwest_middleware::ClientBuilder;
+use crate::core::telemetry::Operation;
+use crate::core::FirebaseError;
 use reqwest_middleware::ClientWithMiddleware;
-use reqwest_retry::policies::ExponentialBackoff;
-use reqwest_retry::RetryTransientMiddleware;
 use thiserror::Error;
 
 /// Error type for Firebase Crashlytics operations.
@@ -34,9 +32,9 @@ pub enum Error {
     /// The middleware encountered an error (e.g., authentication failed).
     #[error("Middleware error: {0}")]
     Middleware(#[from] reqwest_middleware::Error),
-    /// The API returned an error status code.
+    /// The API returned an error.
     #[error("API error: {0}")]
-    Api(StatusCode),
+    Api(FirebaseError),
 }
 
 const CRASHLYTICS_V1_API: &str =
@@ -51,18 +49,9 @@ pub struct FirebaseCrashlytics {
 impl FirebaseCrashlytics {
     /// Creates a new `FirebaseCrashlytics` client.
     pub fn new(middleware: AuthMiddleware) -> Self {
-        let retry_policy = ExponentialBackoff::builder().build_with_max_retries(3);
+        let client = build_client(&middleware, "crashlytics");
 
-        let client = ClientBuilder::new(Client::new())
-            .with(RetryTransientMiddleware::new_with_policy(retry_policy))
-            .with(middleware.clone())
-            .build();
-
-        let project_id = middleware
-            .key
-            .project_id
-            .clone()
-            .unwrap_or_default();
+        let project_id = middleware.project_id().to_string();
 
         let base_url = CRASHLYTICS_V1_API.replace("{project_id}", &project_id);
 
@@ -70,9 +59,9 @@ impl FirebaseCrashlytics {
     }
 
     /// Creates a new `FirebaseCrashlytics` client with a custom client and base URL.
-    /// Internal use only, primarily for testing.
-    #[allow(dead_code)]
-    pub(crate) fn new_with_client(client: ClientWithMiddleware, base_url: String) -> Self {
+    ///
+    /// The client is used as is, so it must add authentication itself.
+    pub fn new_with_client(client: ClientWithMiddleware, base_url: String) -> Self {
         Self { client, base_url }
     }
 
@@ -93,12 +82,14 @@ impl FirebaseCrashlytics {
             self.base_url, app_id, user_id
         );
 
-        let response = self.client.delete(&url).send().await?;
+        let response = self.client.delete(&url).with_extension(Operation("delete_crash_reports")).send().await?;
 
         if response.status().is_success() {
             Ok(())
         } else {
-            Err(Error::Api(response.status()))
+            Err(Error::Api(
+                FirebaseError::from_response(response, "Delete crash reports failed").await,
+            ))
         }
     }
 }
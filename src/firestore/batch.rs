@@ -4,6 +4,8 @@ use super::models::{
 };
 use super::reference::convert_serializable_to_fields;
 use super::FirestoreError;
+use crate::core::telemetry::Operation;
+use crate::core::FirebaseError;
 use reqwest::header;
 use reqwest_middleware::ClientWithMiddleware;
 use serde::Serialize;
@@ -171,8 +173,11 @@ impl<'a> WriteBatch<'a> {
     }
 
     fn extract_resource_name(&self, document_path: &str) -> String {
-        let prefix = "https://firestore.googleapis.com/v1/";
-        let base_path = self.base_url.strip_prefix(prefix).unwrap_or(&self.base_url);
+        let base_path = self
+            .base_url
+            .split_once("/v1/")
+            .map(|(_, path)| path)
+            .unwrap_or(&self.base_url);
         format!("{}/{}", base_path, document_path)
     }
 
@@ -199,18 +204,14 @@ impl<'a> WriteBatch<'a> {
         let response = self
             .client
             .post(&url)
+            .with_extension(Operation("commit"))
             .header(header::CONTENT_TYPE, "application/json")
             .body(serde_json::to_vec(&request)?)
             .send()
             .await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            return Err(FirestoreError::ApiError(format!(
-                "Commit batch failed {}: {}",
-                status, text
-            )));
+            return Err(FirebaseError::from_response(response, "Commit batch failed").await.into());
         }
 
         let result: CommitResponse = response.json().await?;
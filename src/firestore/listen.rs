@@ -1,5 +1,7 @@
 use super::models::{ListenRequest, ListenResponse};
 use super::FirestoreError;
+use crate::core::telemetry::Operation;
+use crate::core::FirebaseError;
 use futures::stream::{self, Stream};
 use reqwest_middleware::ClientWithMiddleware;
 use std::pin::Pin;
@@ -55,7 +57,7 @@ impl Stream for ListenStream {
                 Poll::Ready(None) => {
                     // End of stream.
                     if !self.buffer.is_empty() && !self.buffer.iter().all(|b| b.is_ascii_whitespace()) {
-                         return Poll::Ready(Some(Err(FirestoreError::ApiError("Stream ended with incomplete JSON".into()))));
+                         return Poll::Ready(Some(Err(FirestoreError::InvalidResponse("Stream ended with incomplete JSON".into()))));
                     }
                     return Poll::Ready(None);
                 }
@@ -97,38 +99,18 @@ fn find_json_boundary(buf: &[u8]) -> Option<usize> {
             }
         } else {
             match b {
-                b'{' => {
-                    if !started {
-                        started = true;
-                    }
+                b'{' | b'[' => {
+                    started = true;
                     depth += 1;
                 }
-                b'}' => {
-                    if started {
-                        depth -= 1;
-                        if depth == 0 {
-                            return Some(i + 1);
-                        }
+                b'}' | b']' if started => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(i + 1);
                     }
                 }
-                b'"' => {
-                     if started {
-                        in_string = true;
-                     }
-                }
-                b'[' => {
-                     if !started {
-                        started = true;
-                     }
-                    depth += 1;
-                }
-                b']' => {
-                    if started {
-                        depth -= 1;
-                        if depth == 0 {
-                            return Some(i + 1);
-                        }
-                    }
+                b'"' if started => {
+                    in_string = true;
                 }
                 _ => {}
             }
@@ -154,17 +136,13 @@ pub async fn listen_request(
     // We use a POST request with the ListenRequest in the body
     let response = client
         .post(&url)
+        .with_extension(Operation("listen"))
         .json(request)
         .send()
         .await?;
 
     if !response.status().is_success() {
-        let status = response.status();
-        let text = response.text().await.unwrap_or_default();
-        return Err(FirestoreError::ApiError(format!(
-            "Listen failed {}: {}",
-            status, text
-        )));
+        return Err(FirebaseError::from_response(response, "Listen failed").await.into());
     }
 
     // Use unfold to create a stream from response.chunk()
@@ -24,20 +24,24 @@ mod tests;
 use self::batch::WriteBatch;
 use self::reference::{CollectionReference, DocumentReference};
 use self::transaction::Transaction;
+use crate::core::emulator::emulator_origin;
+use crate::core::http::build_client;
 use crate::core::middleware::AuthMiddleware;
-use crate::core::parse_error_response;
+use crate::core::telemetry::Operation;
+use crate::core::FirebaseError;
 use crate::firestore::models::{
     BeginTransactionRequest, BeginTransactionResponse, ListCollectionIdsRequest,
     ListCollectionIdsResponse, RollbackRequest, TransactionOptions,
 };
-use reqwest::{header, Client};
-use reqwest_middleware::{ClientBuilder, ClientWithMiddleware};
-use reqwest_retry::{policies::ExponentialBackoff, RetryTransientMiddleware};
+use reqwest::header;
+use reqwest_middleware::ClientWithMiddleware;
 use std::future::Future;
 use thiserror::Error;
 
+const FIRESTORE_ORIGIN: &str = "https://firestore.googleapis.com";
 const FIRESTORE_V1_API: &str =
-    "https://firestore.googleapis.com/v1/projects/{project_id}/databases/(default)/documents";
+    "{origin}/v1/projects/{project_id}/databases/{database_id}/documents";
+const DEFAULT_DATABASE_ID: &str = "(default)";
 
 /// Errors that can occur during Firestore operations.
 #[derive(Error, Debug)]
@@ -48,9 +52,27 @@ pub enum FirestoreError {
     /// Wrapper for `reqwest_middleware::Error`.
     #[error("Middleware error: {0}")]
     MiddlewareError(#[from] reqwest_middleware::Error),
-    /// Errors returned by the Firestore API.
+    /// Errors returned by the Firestore API that have no dedicated variant.
     #[error("API error: {0}")]
-    ApiError(String),
+    ApiError(FirebaseError),
+    /// The operation was aborted, typically due to contention with another transaction (`ABORTED`).
+    #[error("Aborted: {0}")]
+    Aborted(FirebaseError),
+    /// The document or resource does not exist (`NOT_FOUND`).
+    #[error("Not found: {0}")]
+    NotFound(FirebaseError),
+    /// The document already exists (`ALREADY_EXISTS`).
+    #[error("Already exists: {0}")]
+    AlreadyExists(FirebaseError),
+    /// A URL could not be built from the given arguments.
+    #[error("Invalid URL: {0}")]
+    InvalidUrl(#[from] url::ParseError),
+    /// The given document or collection path is invalid.
+    #[error("Invalid path: {0}")]
+    InvalidPath(String),
+    /// The server response could not be interpreted.
+    #[error("Invalid response: {0}")]
+    InvalidResponse(String),
     /// Wrapper for `serde_json::Error`.
     #[error("Serialization error: {0}")]
     SerializationError(#[from] serde_json::Error),
@@ -59,6 +81,30 @@ pub enum FirestoreError {
     TransactionError(String),
 }
 
+impl FirestoreError {
+    /// Returns the structured API error behind this error, if it was returned by the server.
+    pub fn firebase_error(&self) -> Option<&FirebaseError> {
+        match self {
+            FirestoreError::ApiError(e)
+            | FirestoreError::Aborted(e)
+            | FirestoreError::NotFound(e)
+            | FirestoreError::AlreadyExists(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<FirebaseError> for FirestoreError {
+    fn from(error: FirebaseError) -> Self {
+        match error.status.as_str() {
+            "ABORTED" => FirestoreError::Aborted(error),
+            "NOT_FOUND" => FirestoreError::NotFound(error),
+            "ALREADY_EXISTS" => FirestoreError::AlreadyExists(error),
+            _ => FirestoreError::ApiError(error),
+        }
+    }
+}
+
 /// Client for interacting with Cloud Firestore.
 pub struct FirebaseFirestore {
     client: ClientWithMiddleware,
@@ -69,34 +115,40 @@ impl FirebaseFirestore {
     /// Creates a new `FirebaseFirestore` instance.
     ///
     /// This is typically called via `FirebaseApp::firestore()`.
+    /// If a Firestore emulator host is configured, the client targets the emulator instead.
     pub fn new(middleware: AuthMiddleware) -> Self {
-        let retry_policy = ExponentialBackoff::builder().build_with_max_retries(3);
+        let (origin, middleware) = match &middleware.emulators().firestore_host {
+            Some(host) => (emulator_origin(host), middleware.for_emulator()),
+            None => (FIRESTORE_ORIGIN.to_string(), middleware),
+        };
 
-        let client = ClientBuilder::new(Client::new())
-            .with(RetryTransientMiddleware::new_with_policy(retry_policy))
-            .with(middleware.clone())
-            .build();
+        let client = build_client(&middleware, "firestore");
 
-        let project_id = middleware.key.project_id.clone().unwrap_or_default();
-        let base_url = FIRESTORE_V1_API.replace("{project_id}", &project_id);
+        let project_id = middleware.project_id().to_string();
+        let database_id = middleware
+            .options()
+            .database_id
+            .clone()
+            .unwrap_or_else(|| DEFAULT_DATABASE_ID.to_string());
+        let base_url = FIRESTORE_V1_API
+            .replace("{origin}", &origin)
+            .replace("{project_id}", &project_id)
+            .replace("{database_id}", &database_id);
 
         Self { client, base_url }
     }
 
     /// Creates a new `FirebaseFirestore` instance with a custom base URL (useful for testing).
     pub fn new_with_url(middleware: AuthMiddleware, base_url: String) -> Self {
-        let retry_policy = ExponentialBackoff::builder().build_with_max_retries(3);
-
-        let client = ClientBuilder::new(Client::new())
-            .with(RetryTransientMiddleware::new_with_policy(retry_policy))
-            .with(middleware.clone())
-            .build();
+        let client = build_client(&middleware, "firestore");
 
         Self { client, base_url }
     }
 
-    #[cfg(test)]
-    pub(crate) fn new_with_client(client: ClientWithMiddleware, base_url: String) -> Self {
+    /// Creates a new `FirebaseFirestore` instance with a custom client and base URL.
+    ///
+    /// The client is used as is, so it must add authentication itself.
+    pub fn new_with_client(client: ClientWithMiddleware, base_url: String) -> Self {
         Self { client, base_url }
     }
 
@@ -127,13 +179,14 @@ impl FirebaseFirestore {
             let response = self
                 .client
                 .post(&url)
+                .with_extension(Operation("list_collections"))
                 .header(header::CONTENT_TYPE, "application/json")
                 .body(serde_json::to_vec(&request)?)
                 .send()
                 .await?;
 
             if !response.status().is_success() {
-                return Err(FirestoreError::ApiError(parse_error_response(response, "List collections failed").await));
+                return Err(FirebaseError::from_response(response, "List collections failed").await.into());
             }
 
             let result: ListCollectionIdsResponse = response.json().await?;
@@ -188,13 +241,14 @@ impl FirebaseFirestore {
         let response = self
             .client
             .post(&url)
+            .with_extension(Operation("begin_transaction"))
             .header(header::CONTENT_TYPE, "application/json")
             .body(serde_json::to_vec(&request)?)
             .send()
             .await?;
 
         if !response.status().is_success() {
-            return Err(FirestoreError::ApiError(parse_error_response(response, "Begin transaction failed").await));
+            return Err(FirebaseError::from_response(response, "Begin transaction failed").await.into());
         }
 
         let result: BeginTransactionResponse = response.json().await?;
@@ -219,13 +273,14 @@ impl FirebaseFirestore {
         let response = self
             .client
             .post(&url)
+            .with_extension(Operation("rollback"))
             .header(header::CONTENT_TYPE, "application/json")
             .body(serde_json::to_vec(&request)?)
             .send()
             .await?;
 
         if !response.status().is_success() {
-            return Err(FirestoreError::ApiError(parse_error_response(response, "Rollback transaction failed").await));
+            return Err(FirebaseError::from_response(response, "Rollback transaction failed").await.into());
         }
 
         Ok(())
@@ -257,8 +312,8 @@ impl FirebaseFirestore {
                 Ok(result) => {
                     match transaction.commit().await {
                          Ok(_) => return Ok(result),
-                         Err(FirestoreError::ApiError(msg)) if msg.contains("ABORTED") || msg.contains("status: 409") || msg.contains("Aborted") => {
-                             // Check for contention (status ABORTED or 409)
+                         Err(FirestoreError::Aborted(_)) => {
+                             // Contention with another transaction
                              retry_count += 1;
                              if retry_count >= max_retries {
                                  return Err(FirestoreError::TransactionError("Max retries reached".into()));
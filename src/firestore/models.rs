@@ -318,6 +318,7 @@ pub struct Target {
 }
 
 /// The type of target to listen to.
+#[allow(clippy::large_enum_variant)]
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub enum TargetType {
@@ -390,10 +391,11 @@ pub struct TargetChange {
 }
 
 /// The type of change.
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum TargetChangeType {
     /// No change has occurred.
+    #[default]
     NoChange,
     /// The targets have been added.
     Add,
@@ -406,11 +408,6 @@ pub enum TargetChangeType {
     Reset,
 }
 
-impl Default for TargetChangeType {
-    fn default() -> Self {
-        TargetChangeType::NoChange
-    }
-}
 
 /// A Document has changed.
 #[derive(Serialize, Deserialize, Debug, Clone)]
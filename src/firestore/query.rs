@@ -9,6 +9,8 @@ use super::reference::{
 };
 use super::snapshot::{DocumentSnapshot, QuerySnapshot};
 use super::FirestoreError;
+use crate::core::telemetry::Operation;
+use crate::core::FirebaseError;
 use reqwest::header;
 use reqwest_middleware::ClientWithMiddleware;
 use serde::Serialize;
@@ -152,18 +154,14 @@ impl<'a> Query<'a> {
         let response = self
             .client
             .post(&url)
+            .with_extension(Operation("run_query"))
             .header(header::CONTENT_TYPE, "application/json")
             .body(serde_json::to_vec(&request)?)
             .send()
             .await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            return Err(FirestoreError::ApiError(format!(
-                "Run query failed {}: {}",
-                status, text
-            )));
+            return Err(FirebaseError::from_response(response, "Run query failed").await.into());
         }
 
         // The response is a stream of JSON objects, e.g. [{...}, {...}] or line-delimited?
@@ -188,7 +186,7 @@ impl<'a> Query<'a> {
                 // Construct DocumentSnapshot
                 // Extract ID from name
                 let name = doc.name.clone();
-                let id = name.split('/').last().unwrap_or_default().to_string();
+                let id = name.split('/').next_back().unwrap_or_default().to_string();
 
                 let doc_ref = DocumentReference {
                     client: self.client,
@@ -7,6 +7,8 @@ use super::models::{
 use super::query::Query;
 use super::snapshot::{DocumentSnapshot, WriteResult};
 use super::FirestoreError;
+use crate::core::telemetry::Operation;
+use crate::core::FirebaseError;
 use reqwest::header;
 use reqwest_middleware::ClientWithMiddleware;
 use serde::de::Error;
@@ -123,7 +125,7 @@ pub(crate) fn convert_serde_value_to_firestore_value(
 // Path format: projects/{project_id}/databases/(default)/documents/...
 pub(crate) fn extract_database_path(path: &str) -> String {
     let parts: Vec<&str> = path.split("/documents").collect();
-    if parts.len() > 0 {
+    if !parts.is_empty() {
         parts[0].to_string()
     } else {
         // Fallback
@@ -161,10 +163,10 @@ impl<'a> DocumentReference<'a> {
     ///
     /// A `Result` containing a `DocumentSnapshot`.
     pub async fn get(&self) -> Result<DocumentSnapshot<'a>, FirestoreError> {
-        let response = self.client.get(&self.path).send().await?;
+        let response = self.client.get(&self.path).with_extension(Operation("get_document")).send().await?;
 
         // Extract ID from path
-        let id = self.path.split('/').last().unwrap_or_default().to_string();
+        let id = self.path.split('/').next_back().unwrap_or_default().to_string();
 
         if response.status() == reqwest::StatusCode::NOT_FOUND {
             return Ok(DocumentSnapshot {
@@ -176,12 +178,7 @@ impl<'a> DocumentReference<'a> {
         }
 
         if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            return Err(FirestoreError::ApiError(format!(
-                "Get document failed {}: {}",
-                status, text
-            )));
+            return Err(FirebaseError::from_response(response, "Get document failed").await.into());
         }
 
         let doc: Document = response.json().await?;
@@ -218,18 +215,14 @@ impl<'a> DocumentReference<'a> {
             let response = self
                 .client
                 .post(&url)
+                .with_extension(Operation("list_collections"))
                 .header(header::CONTENT_TYPE, "application/json")
                 .body(serde_json::to_vec(&request)?)
                 .send()
                 .await?;
 
             if !response.status().is_success() {
-                let status = response.status();
-                let text = response.text().await.unwrap_or_default();
-                return Err(FirestoreError::ApiError(format!(
-                    "List collections failed {}: {}",
-                    status, text
-                )));
+                return Err(FirebaseError::from_response(response, "List collections failed").await.into());
             }
 
             let result: ListCollectionIdsResponse = response.json().await?;
@@ -267,18 +260,14 @@ impl<'a> DocumentReference<'a> {
         let response = self
             .client
             .patch(&url)
+            .with_extension(Operation("set_document"))
             .header(header::CONTENT_TYPE, "application/json")
             .body(body)
             .send()
             .await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            return Err(FirestoreError::ApiError(format!(
-                "Set document failed {}: {}",
-                status, text
-            )));
+            return Err(FirebaseError::from_response(response, "Set document failed").await.into());
         }
 
         let doc: Document = response.json().await?;
@@ -318,18 +307,14 @@ impl<'a> DocumentReference<'a> {
         let response = self
             .client
             .patch(&url)
+            .with_extension(Operation("update_document"))
             .header(header::CONTENT_TYPE, "application/json")
             .body(body)
             .send()
             .await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            return Err(FirestoreError::ApiError(format!(
-                "Update document failed {}: {}",
-                status, text
-            )));
+            return Err(FirebaseError::from_response(response, "Update document failed").await.into());
         }
 
         let doc: Document = response.json().await?;
@@ -340,15 +325,10 @@ impl<'a> DocumentReference<'a> {
 
     /// Deletes the document referred to by this `DocumentReference`.
     pub async fn delete(&self) -> Result<WriteResult, FirestoreError> {
-        let response = self.client.delete(&self.path).send().await?;
+        let response = self.client.delete(&self.path).with_extension(Operation("delete_document")).send().await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            return Err(FirestoreError::ApiError(format!(
-                "Delete document failed {}: {}",
-                status, text
-            )));
+            return Err(FirebaseError::from_response(response, "Delete document failed").await.into());
         }
 
         // Delete returns an empty object on success, or a status.
@@ -408,15 +388,10 @@ impl<'a> CollectionReference<'a> {
 
     /// Lists documents in this collection.
     pub async fn list_documents(&self) -> Result<ListDocumentsResponse, FirestoreError> {
-        let response = self.client.get(&self.path).send().await?;
+        let response = self.client.get(&self.path).with_extension(Operation("list_documents")).send().await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            return Err(FirestoreError::ApiError(format!(
-                "List documents failed {}: {}",
-                status, text
-            )));
+            return Err(FirebaseError::from_response(response, "List documents failed").await.into());
         }
 
         let list: ListDocumentsResponse = response.json().await?;
@@ -435,18 +410,14 @@ impl<'a> CollectionReference<'a> {
         let response = self
             .client
             .post(&self.path)
+            .with_extension(Operation("add_document"))
             .header(header::CONTENT_TYPE, "application/json")
             .body(body)
             .send()
             .await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            return Err(FirestoreError::ApiError(format!(
-                "Add document failed {}: {}",
-                status, text
-            )));
+            return Err(FirebaseError::from_response(response, "Add document failed").await.into());
         }
 
         let doc: Document = response.json().await?;
@@ -492,7 +463,7 @@ impl<'a> CollectionReference<'a> {
     pub async fn listen(&self) -> Result<ListenStream, FirestoreError> {
         let database = extract_database_path(&self.path);
         let (parent, collection_id) = extract_parent_and_collection(&self.path).ok_or_else(|| {
-            FirestoreError::ApiError("Failed to extract parent and collection ID".into())
+            FirestoreError::InvalidPath("Failed to extract parent and collection ID".into())
         })?;
 
         let query_target = QueryTarget {
@@ -119,6 +119,46 @@ async fn test_run_transaction() {
     commit_mock.assert();
 }
 
+#[tokio::test]
+async fn test_run_transaction_retries_aborted_commit() {
+    let server = MockServer::start();
+    let client = ClientBuilder::new(Client::new()).build();
+    let db = FirebaseFirestore::new_with_client(client, server.url("/v1/projects/test-project/databases/(default)/documents"));
+
+    let begin_mock = server.mock(|when, then| {
+        when.method(POST)
+            .path_matches(".*:beginTransaction");
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({ "transaction": "test-transaction-id" }));
+    });
+
+    let commit_mock = server.mock(|when, then| {
+        when.method(POST)
+            .path_matches(".*:commit");
+        then.status(409)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "error": {
+                    "code": 409,
+                    "message": "Transaction lock timeout.",
+                    "status": "ABORTED"
+                }
+            }));
+    });
+
+    let result = db.run_transaction(|transaction| {
+        async move {
+            transaction.update("users/user1", &json!({ "counter": 1 }))?;
+            Ok(())
+        }
+    }).await;
+
+    assert!(matches!(result, Err(FirestoreError::TransactionError(_))));
+    begin_mock.assert_calls(5);
+    commit_mock.assert_calls(5);
+}
+
 #[tokio::test]
 async fn test_get_document() {
     let server = MockServer::start();
@@ -277,4 +317,50 @@ async fn test_collection_add() {
     assert_eq!(doc_ref.path, "projects/test-project/databases/(default)/documents/users/auto-id");
     
     mock.assert();
-}
\ No newline at end of file
+}
+#[tokio::test]
+async fn test_emulator_routing() {
+    let server = MockServer::start();
+    let key = yup_oauth2::ServiceAccountKey {
+        key_type: Some("service_account".to_string()),
+        project_id: Some("test-project".to_string()),
+        private_key_id: None,
+        private_key: String::new(),
+        client_email: "test@example.com".to_string(),
+        client_id: None,
+        auth_uri: None,
+        token_uri: server.url("/token"),
+        auth_provider_x509_cert_url: None,
+        client_x509_cert_url: None,
+    };
+    let emulators = crate::core::emulator::EmulatorConfig::default()
+        .with_firestore_host(server.address().to_string());
+    let middleware = AuthMiddleware::new(key).with_emulators(emulators);
+    let db = FirebaseFirestore::new(middleware);
+
+    let token_mock = server.mock(|when, then| {
+        when.path("/token");
+        then.status(500);
+    });
+
+    let commit_mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/v1/projects/test-project/databases/(default):commit")
+            .header("authorization", "Bearer owner")
+            .json_body(json!({
+                "writes": [
+                    { "delete": "projects/test-project/databases/(default)/documents/users/user1" }
+                ]
+            }));
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({ "writeResults": [{}], "commitTime": "2023-01-01T00:00:00Z" }));
+    });
+
+    let batch = db.batch();
+    batch.delete("users/user1").unwrap();
+    batch.commit().await.unwrap();
+
+    commit_mock.assert();
+    token_mock.assert_calls(0);
+}
@@ -4,7 +4,8 @@ use super::models::{
 };
 use super::reference::{convert_fields_to_serde_value, convert_serializable_to_fields};
 use super::FirestoreError;
-use crate::core::parse_error_response;
+use crate::core::telemetry::Operation;
+use crate::core::FirebaseError;
 use reqwest::header;
 use reqwest_middleware::ClientWithMiddleware;
 use serde::de::DeserializeOwned;
@@ -53,13 +54,14 @@ impl Transaction {
         // But the `base_url` is `https://firestore.../documents`.
         // So we append the relative path.
         let url = format!("{}/{}", self.base_url, document_path);
-        let mut url_obj = Url::parse(&url).map_err(|e| FirestoreError::ApiError(e.to_string()))?;
+        let mut url_obj = Url::parse(&url)?;
         url_obj.query_pairs_mut().append_pair("transaction", &self.transaction_id);
 
         // Add the transaction ID query parameter
         let response = self
             .client
             .get(url_obj)
+            .with_extension(Operation("get_document"))
             .send()
             .await?;
 
@@ -68,7 +70,7 @@ impl Transaction {
         }
 
         if !response.status().is_success() {
-            return Err(FirestoreError::ApiError(parse_error_response(response, "Get document in transaction failed").await));
+            return Err(FirebaseError::from_response(response, "Get document in transaction failed").await.into());
         }
 
         let doc: Document = response.json().await?;
@@ -176,8 +178,11 @@ impl Transaction {
         // document_path: users/alice
         // result: projects/my-project/databases/(default)/documents/users/alice
 
-        let prefix = "https://firestore.googleapis.com/v1/";
-        let base_path = self.base_url.strip_prefix(prefix).unwrap_or(&self.base_url);
+        let base_path = self
+            .base_url
+            .split_once("/v1/")
+            .map(|(_, path)| path)
+            .unwrap_or(&self.base_url);
         format!("{}/{}", base_path, document_path)
     }
 
@@ -206,13 +211,14 @@ impl Transaction {
         let response = self
             .client
             .post(&url)
+            .with_extension(Operation("commit"))
             .header(header::CONTENT_TYPE, "application/json")
             .body(serde_json::to_vec(&request)?)
             .send()
             .await?;
 
         if !response.status().is_success() {
-            return Err(FirestoreError::ApiError(parse_error_response(response, "Commit transaction failed").await));
+            return Err(FirebaseError::from_response(response, "Commit transaction failed").await.into());
         }
 
         let result: CommitResponse = response.json().await?;
@@ -5,6 +5,7 @@
 //!
 //! ## Modules
 //!
+//! - [`app_check`]: Firebase App Check (Verify and create App Check tokens).
 //! - [`auth`]: Firebase Authentication (User management, ID token verification).
 //! - [`messaging`]: Firebase Cloud Messaging (Send messages, topic management).
 //! - [`remote_config`]: Firebase Remote Config (Get, update, rollback configurations).
@@ -15,7 +16,8 @@
 //! ## Usage
 //!
 //! The entry point is the [`FirebaseApp`] struct. You initialize it with a `ServiceAccountKey`
-//! (typically loaded from a JSON file), and then access the various services.
+//! (typically loaded from a JSON file) or any other [`core::credential::Credential`],
+//! and then access the various services.
 //!
 //! ```rust,no_run
 //! use firebase_admin_sdk::{FirebaseApp, yup_oauth2};
@@ -23,14 +25,29 @@
 //! async fn example() {
 //!     let key = yup_oauth2::read_service_account_key("service-account.json").await.unwrap();
 //!
-//!     let app = FirebaseApp::new(key);
+//!     let app = FirebaseApp::new(key).unwrap();
 //!     let auth = app.auth();
 //!     let messaging = app.messaging();
 //! }
 //! ```
+//!
+//! For more control over the HTTP stack (shared connection pool, timeouts, proxy, retries),
+//! use [`FirebaseApp::builder`].
+//!
+//! ## Emulators
+//!
+//! Auth, Firestore and Storage clients are routed to the Firebase Emulator Suite when the
+//! `FIREBASE_AUTH_EMULATOR_HOST`, `FIRESTORE_EMULATOR_HOST` or `FIREBASE_STORAGE_EMULATOR_HOST`
+//! environment variables are set, or when an explicit [`core::emulator::EmulatorConfig`] is
+//! passed to [`FirebaseApp::with_emulators`].
 
+#[cfg(feature = "app_check")]
+pub mod app_check;
 #[cfg(feature = "auth")]
 pub mod auth;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+pub mod builder;
 pub mod core;
 #[cfg(feature = "crashlytics")]
 pub mod crashlytics;
@@ -42,13 +59,23 @@ pub mod messaging;
 pub mod remote_config;
 #[cfg(feature = "storage")]
 pub mod storage;
+#[cfg(feature = "testing")]
+pub mod testing;
+
+pub use builder::FirebaseAppBuilder;
 
 // Re-export yup_oauth2 for user convenience so they don't need to add it separately
 pub use yup_oauth2;
 
+#[cfg(feature = "app_check")]
+use app_check::FirebaseAppCheck;
 #[cfg(feature = "auth")]
 use auth::FirebaseAuth;
+use builder::BuildError;
+use core::credential::{AccessToken, ApplicationDefaultCredential, Credential, CredentialError};
+use core::emulator::EmulatorConfig;
 use core::middleware::AuthMiddleware;
+use core::options::AppOptions;
 #[cfg(feature = "crashlytics")]
 use crashlytics::FirebaseCrashlytics;
 #[cfg(feature = "firestore")]
@@ -59,8 +86,30 @@ use messaging::FirebaseMessaging;
 use remote_config::FirebaseRemoteConfig;
 #[cfg(feature = "storage")]
 use storage::FirebaseStorage;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use thiserror::Error;
 use yup_oauth2::ServiceAccountKey;
 
+/// The name of the app returned by [`FirebaseApp::get_app`] when no name is given.
+pub const DEFAULT_APP_NAME: &str = "[DEFAULT]";
+
+/// Errors that can occur when working with the app registry.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum AppError {
+    /// An app with the given name has already been initialized.
+    #[error("Firebase app named '{0}' already exists")]
+    DuplicateApp(String),
+    /// No app with the given name has been initialized.
+    #[error("Firebase app named '{0}' does not exist")]
+    NoApp(String),
+}
+
+fn registry() -> &'static Mutex<HashMap<String, FirebaseApp>> {
+    static APPS: OnceLock<Mutex<HashMap<String, FirebaseApp>>> = OnceLock::new();
+    APPS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
 /// The entry point for the Firebase Admin SDK.
 ///
 /// `FirebaseApp` holds the service account credentials and acts as a factory for creating
@@ -69,7 +118,13 @@ use yup_oauth2::ServiceAccountKey;
 /// It uses a "synchronous constructor, lazy async authentication" pattern.
 /// The `new` method is synchronous and cheap, while the actual OAuth2 authentication
 /// happens asynchronously and lazily upon the first API request made by any service client.
+///
+/// Apps are cheap to clone; clones share credentials, token caches and the HTTP connection pool.
+/// Several apps (e.g., one per project) can be kept in a process-wide registry with
+/// [`FirebaseApp::initialize_named`] and looked up again with [`FirebaseApp::get_app`].
+#[derive(Clone)]
 pub struct FirebaseApp {
+    name: String,
     middleware: AuthMiddleware,
 }
 
@@ -82,18 +137,182 @@ impl FirebaseApp {
     /// # Arguments
     ///
     /// * `service_account_key` - A `yup_oauth2::ServiceAccountKey` struct containing the credentials.
-    pub fn new(service_account_key: ServiceAccountKey) -> Self {
+    ///
+    /// Emulator hosts are read from the `FIREBASE_AUTH_EMULATOR_HOST`, `FIRESTORE_EMULATOR_HOST`
+    /// and `FIREBASE_STORAGE_EMULATOR_HOST` environment variables.
+    ///
+    /// Fails with [`BuildError::ProjectIdRequired`] if the key has no project ID and none is
+    /// set in the environment.
+    pub fn new(service_account_key: ServiceAccountKey) -> Result<Self, BuildError> {
+        Self::from_middleware(AuthMiddleware::new(service_account_key))
+    }
+
+    /// Returns a [`FirebaseAppBuilder`] for configuring credentials, the shared HTTP client,
+    /// timeouts, proxy, user agent, retry policy and extra middleware layers.
+    pub fn builder() -> FirebaseAppBuilder {
+        FirebaseAppBuilder::new()
+    }
+
+    /// Creates a new `FirebaseApp` instance backed by an arbitrary [`Credential`].
+    ///
+    /// Like [`FirebaseApp::new`], this method is synchronous; the credential is only asked
+    /// for a token when a service performs its first request.
+    ///
+    /// # Arguments
+    ///
+    /// * `credential` - The credential used to authorize requests.
+    ///
+    /// Fails with [`BuildError::ProjectIdRequired`] if neither the credential nor the
+    /// environment provides a project ID; use [`FirebaseAppBuilder::build_async`] to look it
+    /// up from the metadata server.
+    pub fn from_credential(credential: impl Credential + 'static) -> Result<Self, BuildError> {
+        Self::from_middleware(AuthMiddleware::from_credential(Arc::new(credential)))
+    }
+
+    /// Creates a new `FirebaseApp` instance using Application Default Credentials.
+    ///
+    /// See [`ApplicationDefaultCredential`] for the resolution order. This is the
+    /// recommended way to run on Cloud Run, GKE or Compute Engine without shipping key files.
+    /// On those platforms, set `GOOGLE_CLOUD_PROJECT` or use
+    /// [`FirebaseAppBuilder::build_async`], which asks the metadata server for the project ID.
+    pub fn application_default() -> Result<Self, BuildError> {
+        Self::from_credential(ApplicationDefaultCredential::new()?)
+    }
+
+    fn from_middleware(middleware: AuthMiddleware) -> Result<Self, BuildError> {
+        if middleware.project_id().is_empty() {
+            return Err(BuildError::ProjectIdRequired);
+        }
+        Ok(Self {
+            name: DEFAULT_APP_NAME.to_string(),
+            middleware: middleware.with_emulators(EmulatorConfig::from_env()),
+        })
+    }
+
+    /// Overrides the emulator hosts detected from the environment.
+    ///
+    /// Services with a configured host are routed to the local Firebase Emulator Suite
+    /// and authenticate with the emulator `owner` token.
+    ///
+    /// # Arguments
+    ///
+    /// * `emulators` - The emulator hosts to use.
+    pub fn with_emulators(self, emulators: EmulatorConfig) -> Self {
         Self {
-            middleware: AuthMiddleware::new(service_account_key),
+            middleware: self.middleware.with_emulators(emulators),
+            ..self
         }
     }
 
+    /// Applies project, bucket, database and service account overrides.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - The overrides honoured by every service client created from this app.
+    pub fn with_options(self, options: AppOptions) -> Self {
+        Self {
+            middleware: self.middleware.with_options(options),
+            ..self
+        }
+    }
+
+    /// Returns the name of this app.
+    ///
+    /// Apps that were not registered with [`FirebaseApp::initialize_named`] are named
+    /// [`DEFAULT_APP_NAME`].
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the options of this app.
+    pub fn options(&self) -> &AppOptions {
+        self.middleware.options()
+    }
+
+    /// Returns the project ID used by this app's service clients.
+    pub fn project_id(&self) -> &str {
+        self.middleware.project_id()
+    }
+
+    /// Returns a valid OAuth2 access token and its expiry.
+    ///
+    /// The token is the one attached to API requests; it is served from the token cache or
+    /// the credential's own cache while it is fresh.
+    pub async fn access_token(&self) -> Result<AccessToken, CredentialError> {
+        self.middleware.access_token().await
+    }
+
+    /// Fetches an access token ahead of the first API call.
+    ///
+    /// Call this at startup to surface credential problems early and to keep token
+    /// acquisition latency off the first request.
+    pub async fn prewarm(&self) -> Result<(), CredentialError> {
+        self.middleware.access_token().await.map(|_| ())
+    }
+
+    /// Registers an app under the given name and returns the registered instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The unique name of the app, e.g. [`DEFAULT_APP_NAME`] or a project alias.
+    /// * `app` - The app to register.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AppError::DuplicateApp`] if an app with the same name is already registered.
+    pub fn initialize_named(name: impl Into<String>, app: FirebaseApp) -> Result<Self, AppError> {
+        let name = name.into();
+        let mut apps = registry().lock().unwrap_or_else(|e| e.into_inner());
+        if apps.contains_key(&name) {
+            return Err(AppError::DuplicateApp(name));
+        }
+
+        let app = Self {
+            name: name.clone(),
+            ..app
+        };
+        apps.insert(name, app.clone());
+        Ok(app)
+    }
+
+    /// Returns the registered app with the given name.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AppError::NoApp`] if no app with that name is registered.
+    pub fn get_app(name: &str) -> Result<Self, AppError> {
+        let apps = registry().lock().unwrap_or_else(|e| e.into_inner());
+        apps.get(name)
+            .cloned()
+            .ok_or_else(|| AppError::NoApp(name.to_string()))
+    }
+
+    /// Removes the app with the given name from the registry.
+    ///
+    /// Clones of the app that are still held elsewhere keep working.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AppError::NoApp`] if no app with that name is registered.
+    pub fn delete_app(name: &str) -> Result<(), AppError> {
+        let mut apps = registry().lock().unwrap_or_else(|e| e.into_inner());
+        apps.remove(name)
+            .map(|_| ())
+            .ok_or_else(|| AppError::NoApp(name.to_string()))
+    }
+
     /// Returns a client for interacting with Firebase Authentication.
     #[cfg(feature = "auth")]
     pub fn auth(&self) -> FirebaseAuth {
         FirebaseAuth::new(self.middleware.clone())
     }
 
+    /// Returns a client for verifying and creating Firebase App Check tokens.
+    #[cfg(feature = "app_check")]
+    pub fn app_check(&self) -> FirebaseAppCheck {
+        FirebaseAppCheck::new(self.middleware.clone())
+    }
+
     /// Returns a client for interacting with Firebase Cloud Messaging (FCM).
     #[cfg(feature = "messaging")]
     pub fn messaging(&self) -> FirebaseMessaging {
@@ -25,11 +25,12 @@
 //! # }
 //! ```
 
-use reqwest::{Client, header};
-use reqwest_middleware::{ClientBuilder, ClientWithMiddleware};
-use reqwest_retry::{RetryTransientMiddleware, policies::ExponentialBackoff};
+use reqwest::header;
+use reqwest_middleware::ClientWithMiddleware;
+use crate::core::http::build_client;
 use crate::core::middleware::AuthMiddleware;
-use crate::core::parse_error_response;
+use crate::core::telemetry::Operation;
+use crate::core::FirebaseError;
 use crate::messaging::models::{Message, MulticastMessage, TopicManagementResponse, TopicManagementError, BatchResponse, SendResponse, SendResponseInternal};
 use thiserror::Error;
 use serde::{Deserialize, Serialize};
@@ -48,9 +49,27 @@ pub enum MessagingError {
     /// Wrapper for `reqwest_middleware::Error`.
     #[error("Middleware error: {0}")]
     MiddlewareError(#[from] reqwest_middleware::Error),
-    /// Errors returned by the FCM API.
+    /// Errors returned by the FCM API that have no dedicated variant.
     #[error("API error: {0}")]
-    ApiError(String),
+    ApiError(FirebaseError),
+    /// The message was rejected before being sent because it is malformed.
+    #[error("Invalid message: {0}")]
+    InvalidMessage(String),
+    /// The request contained an invalid argument, such as an invalid payload (`INVALID_ARGUMENT`).
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(FirebaseError),
+    /// The registration token is no longer valid and should be removed (`UNREGISTERED`).
+    #[error("Unregistered: {0}")]
+    Unregistered(FirebaseError),
+    /// The registration token belongs to a different sender (`SENDER_ID_MISMATCH`).
+    #[error("Sender ID mismatch: {0}")]
+    SenderIdMismatch(FirebaseError),
+    /// The sending rate exceeded the message quota (`QUOTA_EXCEEDED`).
+    #[error("Quota exceeded: {0}")]
+    QuotaExceeded(FirebaseError),
+    /// The APNs certificate or Web Push key is invalid or missing (`THIRD_PARTY_AUTH_ERROR`).
+    #[error("Third party authentication error: {0}")]
+    ThirdPartyAuthError(FirebaseError),
     /// Wrapper for `serde_json::Error`.
     #[error("Serialization error: {0}")]
     SerializationError(#[from] serde_json::Error),
@@ -59,6 +78,34 @@ pub enum MessagingError {
     MultipartError(String),
 }
 
+impl MessagingError {
+    /// Returns the structured API error behind this error, if it was returned by the server.
+    pub fn firebase_error(&self) -> Option<&FirebaseError> {
+        match self {
+            MessagingError::ApiError(e)
+            | MessagingError::InvalidArgument(e)
+            | MessagingError::Unregistered(e)
+            | MessagingError::SenderIdMismatch(e)
+            | MessagingError::QuotaExceeded(e)
+            | MessagingError::ThirdPartyAuthError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<FirebaseError> for MessagingError {
+    fn from(error: FirebaseError) -> Self {
+        match error.code.as_deref() {
+            Some("INVALID_ARGUMENT") => MessagingError::InvalidArgument(error),
+            Some("UNREGISTERED") => MessagingError::Unregistered(error),
+            Some("SENDER_ID_MISMATCH") => MessagingError::SenderIdMismatch(error),
+            Some("QUOTA_EXCEEDED") => MessagingError::QuotaExceeded(error),
+            Some("THIRD_PARTY_AUTH_ERROR") => MessagingError::ThirdPartyAuthError(error),
+            _ => MessagingError::ApiError(error),
+        }
+    }
+}
+
 /// Client for interacting with Firebase Cloud Messaging.
 #[derive(Clone)]
 pub struct FirebaseMessaging {
@@ -98,14 +145,9 @@ impl FirebaseMessaging {
     ///
     /// This is typically called via `FirebaseApp::messaging()`.
     pub fn new(middleware: AuthMiddleware) -> Self {
-        let retry_policy = ExponentialBackoff::builder().build_with_max_retries(3);
-
-        let client = ClientBuilder::new(Client::new())
-            .with(RetryTransientMiddleware::new_with_policy(retry_policy))
-            .with(middleware.clone())
-            .build();
+        let client = build_client(&middleware, "messaging");
 
-        let project_id = middleware.key.project_id.clone().unwrap_or_default();
+        let project_id = middleware.project_id().to_string();
         let base_url = format!("https://fcm.googleapis.com/v1/projects/{}/messages:send", project_id);
         let batch_url = "https://fcm.googleapis.com/batch".to_string();
         let iid_base_url = "https://iid.googleapis.com".to_string();
@@ -119,14 +161,11 @@ impl FirebaseMessaging {
         }
     }
 
-    #[cfg(test)]
-    pub(crate) fn new_with_url(middleware: AuthMiddleware, base_url: String, batch_url: String, iid_base_url: String) -> Self {
-        let retry_policy = ExponentialBackoff::builder().build_with_max_retries(3);
-        let client = ClientBuilder::new(Client::new())
-            .with(RetryTransientMiddleware::new_with_policy(retry_policy))
-            .with(middleware.clone())
-            .build();
-        let project_id = middleware.key.project_id.clone().unwrap_or_default();
+    /// Creates a new `FirebaseMessaging` instance with custom endpoints for sending, batch
+    /// sending and topic management (useful for testing).
+    pub fn new_with_url(middleware: AuthMiddleware, base_url: String, batch_url: String, iid_base_url: String) -> Self {
+        let client = build_client(&middleware, "messaging");
+        let project_id = middleware.project_id().to_string();
         Self { client, project_id, base_url, batch_url, iid_base_url }
     }
 
@@ -153,7 +192,7 @@ impl FirebaseMessaging {
         .count();
 
         if num_targets != 1 {
-            return Err(MessagingError::ApiError(
+            return Err(MessagingError::InvalidMessage(
                 "Message must have exactly one of token, topic, or condition.".to_string(),
             ));
         }
@@ -170,13 +209,14 @@ impl FirebaseMessaging {
 
         let response = self.client
             .post(&self.base_url)
+            .with_extension(Operation("send"))
             .header(header::CONTENT_TYPE, "application/json")
             .body(serde_json::to_vec(&request)?)
             .send()
             .await?;
 
         if !response.status().is_success() {
-            return Err(MessagingError::ApiError(parse_error_response(response, "FCM send failed").await));
+            return Err(FirebaseError::from_response(response, "FCM send failed").await.into());
         }
 
         let result: SendResponseInternal = response.json().await?;
@@ -204,7 +244,7 @@ impl FirebaseMessaging {
         }
 
         if messages.len() > 500 {
-            return Err(MessagingError::ApiError("Cannot send more than 500 messages in a single batch.".to_string()));
+            return Err(MessagingError::InvalidMessage("Cannot send more than 500 messages in a single batch.".to_string()));
         }
 
         let url = self.batch_url.clone();
@@ -216,13 +256,14 @@ impl FirebaseMessaging {
 
         let response = self.client
             .post(&url)
+            .with_extension(Operation("send_each"))
             .header(header::CONTENT_TYPE, content_type)
             .body(body)
             .send()
             .await?;
 
         if !response.status().is_success() {
-            return Err(MessagingError::ApiError(parse_error_response(response, "FCM batch send failed").await));
+            return Err(FirebaseError::from_response(response, "FCM batch send failed").await.into());
         }
 
         let multipart_boundary = response
@@ -310,14 +351,16 @@ impl FirebaseMessaging {
                             Err(_) => return Err(MessagingError::MultipartError("Failed to parse successful response part".to_string())),
                         }
                     } else { // It's an error response
-                         match serde_json::from_str::<serde_json::Value>(json_body) {
-                            Ok(error_response) => responses.push(SendResponse {
-                                success: false,
-                                message_id: None,
-                                error: Some(error_response.to_string()),
-                            }),
-                            Err(_) => return Err(MessagingError::MultipartError("Failed to parse error response part".to_string())),
-                        }
+                        let http_status = status_line
+                            .split_whitespace()
+                            .nth(1)
+                            .and_then(|code| code.parse::<u16>().ok())
+                            .ok_or_else(|| MessagingError::MultipartError("Invalid status line in response part".to_string()))?;
+                        responses.push(SendResponse {
+                            success: false,
+                            message_id: None,
+                            error: Some(FirebaseError::from_body(http_status, json_body, "FCM send failed")),
+                        });
                     }
                 } else {
                      return Err(MessagingError::MultipartError("Invalid inner HTTP response format".to_string()));
@@ -398,6 +441,7 @@ impl FirebaseMessaging {
 
             let response = self.client
                 .post(&url)
+                .with_extension(Operation("manage_topic"))
                 .header(header::CONTENT_TYPE, "application/json")
                 // Use access_token_header from AuthMiddleware, but the IID API also requires the standard header.
                 // The AuthMiddleware adds it automatically.
@@ -407,7 +451,7 @@ impl FirebaseMessaging {
                 .await?;
 
             if !response.status().is_success() {
-                return Err(MessagingError::ApiError(parse_error_response(response, "Topic management failed").await));
+                return Err(FirebaseError::from_response(response, "Topic management failed").await.into());
             }
 
             let api_response: TopicManagementApiResponse = response.json().await?;
@@ -1,3 +1,4 @@
+use crate::core::FirebaseError;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -412,6 +413,7 @@ pub struct Aps {
 }
 
 /// An alert which can be a string or a dictionary.
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum ApsAlert {
@@ -521,8 +523,11 @@ pub struct SendResponse {
     pub success: bool,
     /// The message ID, if sent successfully.
     pub message_id: Option<String>,
-    /// The error message, if failed.
-    pub error: Option<String>,
+    /// The error, if failed.
+    ///
+    /// Convert it with `MessagingError::from` to match on typed variants such as
+    /// `MessagingError::Unregistered`.
+    pub error: Option<FirebaseError>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -11,12 +11,12 @@ pub mod models;
 #[cfg(test)]
 mod tests;
 
+use crate::core::http::build_client;
 use crate::core::middleware::AuthMiddleware;
+use crate::core::telemetry::Operation;
+use crate::core::FirebaseError;
 use crate::remote_config::models::RemoteConfig;
-use reqwest::Client;
-use reqwest_middleware::{ClientBuilder, ClientWithMiddleware};
-use reqwest_retry::policies::ExponentialBackoff;
-use reqwest_retry::RetryTransientMiddleware;
+use reqwest_middleware::ClientWithMiddleware;
 use url::Url;
 
 /// Client for interacting with Firebase Remote Config.
@@ -28,18 +28,6 @@ pub struct FirebaseRemoteConfig {
 const REMOTE_CONFIG_V1_API: &str =
     "https://firebaseremoteconfig.googleapis.com/v1/projects/{project_id}/remoteConfig";
 
-#[derive(Debug, serde::Deserialize)]
-struct ApiError {
-    code: u16,
-    message: String,
-    status: String,
-}
-
-#[derive(Debug, serde::Deserialize)]
-struct ErrorWrapper {
-    error: ApiError,
-}
-
 /// Errors that can occur during Remote Config operations.
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -56,12 +44,11 @@ pub enum Error {
     #[error("an error occurred while serializing/deserializing JSON: {0}")]
     Json(#[from] serde_json::Error),
     /// Error returned by the Remote Config API.
-    #[error("the firebase API returned an error: {code} {status}: {message}")]
-    Api {
-        code: u16,
-        message: String,
-        status: String,
-    },
+    #[error("the firebase API returned an error: {0}")]
+    Api(FirebaseError),
+    /// A URL could not be built from the given arguments.
+    #[error("invalid URL: {0}")]
+    Url(#[from] url::ParseError),
 }
 
 impl FirebaseRemoteConfig {
@@ -69,23 +56,18 @@ impl FirebaseRemoteConfig {
     ///
     /// This is typically called via `FirebaseApp::remote_config()`.
     pub fn new(middleware: AuthMiddleware) -> Self {
-        let retry_policy = ExponentialBackoff::builder().build_with_max_retries(3);
-
-        let client = ClientBuilder::new(Client::new())
-            .with(RetryTransientMiddleware::new_with_policy(retry_policy))
-            .with(middleware.clone())
-            .build();
+        let client = build_client(&middleware, "remote_config");
 
-        let project_id = middleware.key.project_id.clone().unwrap_or_default();
+        let project_id = middleware.project_id().to_string();
         let base_url = REMOTE_CONFIG_V1_API.replace("{project_id}", &project_id);
 
         Self { client, base_url }
     }
 
     /// Creates a new `FirebaseRemoteConfig` instance with a custom client and base URL.
-    /// Internal use only, primarily for testing.
-    #[allow(dead_code)]
-    pub(crate) fn new_with_client(client: ClientWithMiddleware, base_url: String) -> Self {
+    ///
+    /// The client is used as is, so it must add authentication itself.
+    pub fn new_with_client(client: ClientWithMiddleware, base_url: String) -> Self {
         Self { client, base_url }
     }
 
@@ -96,12 +78,9 @@ impl FirebaseRemoteConfig {
         if response.status().is_success() {
             Ok(response.json().await?)
         } else {
-            let error: ErrorWrapper = response.json().await?;
-            Err(Error::Api {
-                code: error.error.code,
-                message: error.error.message,
-                status: error.error.status,
-            })
+            Err(Error::Api(
+                FirebaseError::from_response(response, "Remote Config request failed").await,
+            ))
         }
     }
 
@@ -111,12 +90,9 @@ impl FirebaseRemoteConfig {
     ) -> Result<(T, Option<String>), Error> {
         let response = req.send().await?;
         if !response.status().is_success() {
-            let error: ErrorWrapper = response.json().await?;
-            return Err(Error::Api {
-                code: error.error.code,
-                message: error.error.message,
-                status: error.error.status,
-            });
+            return Err(Error::Api(
+                FirebaseError::from_response(response, "Remote Config request failed").await,
+            ));
         }
         let etag = response
             .headers()
@@ -131,7 +107,7 @@ impl FirebaseRemoteConfig {
     ///
     /// The returned `RemoteConfig` object contains an ETag which is used for optimistic locking during updates.
     pub async fn get(&self) -> Result<RemoteConfig, Error> {
-        let req = self.client.get(&self.base_url);
+        let req = self.client.get(&self.base_url).with_extension(Operation("get_template"));
         let (mut config, etag) = self.request::<RemoteConfig>(req).await?;
         if let Some(e) = etag {
             config.etag = e;
@@ -151,6 +127,7 @@ impl FirebaseRemoteConfig {
         let req = self
             .client
             .put(&self.base_url)
+            .with_extension(Operation("publish_template"))
             .header("If-Match", config.etag.clone())
             .json(&config);
         let (mut config, etag) = self.request::<RemoteConfig>(req).await?;
@@ -170,11 +147,7 @@ impl FirebaseRemoteConfig {
         options: Option<models::ListVersionsOptions>,
     ) -> Result<models::ListVersionsResult, Error> {
         let url = format!("{}/versions", self.base_url);
-        let mut url_obj = Url::parse(&url).map_err(|e| Error::Api {
-            code: 0,
-            message: e.to_string(),
-            status: "INTERNAL".to_string(),
-        })?;
+        let mut url_obj = Url::parse(&url)?;
 
         if let Some(opts) = options {
             let mut query_pairs = url_obj.query_pairs_mut();
@@ -186,7 +159,7 @@ impl FirebaseRemoteConfig {
             }
         }
 
-        let response = self.client.get(url_obj).send().await?;
+        let response = self.client.get(url_obj).with_extension(Operation("list_versions")).send().await?;
         self.process_response(response).await
     }
 
@@ -199,7 +172,7 @@ impl FirebaseRemoteConfig {
         let url = format!("{}:rollback", self.base_url);
         let body = models::RollbackRequest { version_number };
 
-        let req = self.client.post(url).json(&body);
+        let req = self.client.post(url).with_extension(Operation("rollback")).json(&body);
         let (mut config, etag) = self.request::<RemoteConfig>(req).await?;
         if let Some(e) = etag {
             config.etag = e;
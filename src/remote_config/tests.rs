@@ -157,7 +157,6 @@ async fn test_list_versions() {
     let result = rc.list_versions(Some(crate::remote_config::models::ListVersionsOptions {
         page_size: Some(10),
         page_token: None,
-        ..Default::default()
     })).await.unwrap();
 
     assert_eq!(result.versions.len(), 2);
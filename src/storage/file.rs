@@ -1,10 +1,9 @@
 use crate::core::middleware::AuthMiddleware;
+use crate::core::telemetry::Operation;
+use crate::core::FirebaseError;
 use crate::storage::StorageError;
 use reqwest::header;
 use reqwest_middleware::ClientWithMiddleware;
-use rsa::pkcs1::DecodeRsaPrivateKey;
-use rsa::pkcs8::DecodePrivateKey;
-use rsa::{Pkcs1v15Sign, RsaPrivateKey};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::time::{SystemTime, Duration};
@@ -137,17 +136,15 @@ impl File {
 
     /// Generates a V4 signed URL for accessing the file.
     ///
+    /// The URL is signed by the app's signer, locally or through the IAM Credentials API when
+    /// the credential has no private key.
+    ///
     /// # Arguments
     ///
     /// * `options` - The options for generating the signed URL.
-    pub fn get_signed_url(&self, options: GetSignedUrlOptions) -> Result<String, StorageError> {
-        let key = &self.middleware.key;
-        let client_email = &key.client_email;
-        let private_key_pem = &key.private_key;
-
-        if client_email.is_empty() || private_key_pem.is_empty() {
-            return Err(StorageError::ProjectIdMissing); // Using existing error enum, though maybe not precise
-        }
+    pub async fn get_signed_url(&self, options: GetSignedUrlOptions) -> Result<String, StorageError> {
+        let signer = self.middleware.signer()?;
+        let client_email = signer.account_email().await?;
 
                 let now = SystemTime::now();
 
@@ -178,7 +175,7 @@ impl File {
             .unwrap_or(Duration::from_secs(0))
             .as_secs();
 
-        let mut query_params = vec![
+        let mut query_params = [
             ("X-Goog-Algorithm", "GOOG4-RSA-SHA256".to_string()),
             (
                 "X-Goog-Credential",
@@ -221,21 +218,7 @@ impl File {
             algorithm, iso_date, credential_scope, request_hash_hex
         );
 
-        let hash_to_sign = Sha256::digest(string_to_sign.as_bytes());
-
-        let priv_key = if private_key_pem.contains("BEGIN RSA PRIVATE KEY") {
-            RsaPrivateKey::from_pkcs1_pem(private_key_pem).map_err(|e| {
-                StorageError::ApiError(format!("Invalid private key (PKCS1): {}", e))
-            })?
-        } else {
-            RsaPrivateKey::from_pkcs8_pem(private_key_pem).map_err(|e| {
-                StorageError::ApiError(format!("Invalid private key (PKCS8): {}", e))
-            })?
-        };
-
-        let signature = priv_key
-            .sign(Pkcs1v15Sign::new::<Sha256>(), &hash_to_sign)
-            .map_err(|e| StorageError::ApiError(format!("Signing failed: {}", e)))?;
+        let signature = signer.sign(string_to_sign.as_bytes()).await?;
 
         let signature_hex = hex::encode(signature);
 
@@ -312,7 +295,7 @@ impl File {
             }
         };
 
-        let mut url_obj = Url::parse(&url).map_err(|e| StorageError::ApiError(e.to_string()))?;
+        let mut url_obj = Url::parse(&url)?;
         url_obj
             .query_pairs_mut()
             .append_pair("uploadType", "media")
@@ -321,18 +304,14 @@ impl File {
         let response = self
             .client
             .post(url_obj)
+            .with_extension(Operation("save"))
             .header(header::CONTENT_TYPE, mime_type)
             .body(body)
             .send()
             .await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            return Err(StorageError::ApiError(format!(
-                "Upload failed {}: {}",
-                status, text
-            )));
+            return Err(FirebaseError::from_response(response, "Upload failed").await.into());
         }
 
         Ok(())
@@ -349,18 +328,13 @@ impl File {
             self.base_url, self.bucket_name, encoded_name
         );
 
-        let mut url_obj = Url::parse(&url).map_err(|e| StorageError::ApiError(e.to_string()))?;
+        let mut url_obj = Url::parse(&url)?;
         url_obj.query_pairs_mut().append_pair("alt", "media");
 
-        let response = self.client.get(url_obj).send().await?;
+        let response = self.client.get(url_obj).with_extension(Operation("download")).send().await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            return Err(StorageError::ApiError(format!(
-                "Download failed {}: {}",
-                status, text
-            )));
+            return Err(FirebaseError::from_response(response, "Download failed").await.into());
         }
 
         Ok(response.bytes().await?)
@@ -375,15 +349,10 @@ impl File {
             self.base_url, self.bucket_name, encoded_name
         );
 
-        let response = self.client.delete(&url).send().await?;
+        let response = self.client.delete(&url).with_extension(Operation("delete")).send().await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            return Err(StorageError::ApiError(format!(
-                "Delete failed {}: {}",
-                status, text
-            )));
+            return Err(FirebaseError::from_response(response, "Delete failed").await.into());
         }
 
         Ok(())
@@ -398,15 +367,10 @@ impl File {
             self.base_url, self.bucket_name, encoded_name
         );
 
-        let response = self.client.get(&url).send().await?;
+        let response = self.client.get(&url).with_extension(Operation("get_metadata")).send().await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            return Err(StorageError::ApiError(format!(
-                "Get metadata failed {}: {}",
-                status, text
-            )));
+            return Err(FirebaseError::from_response(response, "Get metadata failed").await.into());
         }
 
         Ok(response.json().await?)
@@ -431,18 +395,14 @@ impl File {
         let response = self
             .client
             .patch(&url)
+            .with_extension(Operation("set_metadata"))
             .header(header::CONTENT_TYPE, "application/json")
             .json(metadata)
             .send()
             .await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            return Err(StorageError::ApiError(format!(
-                "Set metadata failed {}: {}",
-                status, text
-            )));
+            return Err(FirebaseError::from_response(response, "Set metadata failed").await.into());
         }
 
         Ok(response.json().await?)
@@ -22,14 +22,17 @@
 pub mod bucket;
 pub mod file;
 
+use crate::core::emulator::emulator_origin;
+use crate::core::http::build_client;
 use crate::core::middleware::AuthMiddleware;
+use crate::core::signer::SignerError;
+use crate::core::FirebaseError;
 use bucket::Bucket;
-use reqwest::Client;
-use reqwest_middleware::{ClientBuilder, ClientWithMiddleware};
-use reqwest_retry::{policies::ExponentialBackoff, RetryTransientMiddleware};
+use reqwest_middleware::ClientWithMiddleware;
 use thiserror::Error;
 
-const STORAGE_V1_API: &str = "https://storage.googleapis.com/storage/v1";
+const STORAGE_ORIGIN: &str = "https://storage.googleapis.com";
+const STORAGE_V1_API: &str = "{origin}/storage/v1";
 
 /// Errors that can occur during Storage operations.
 #[derive(Error, Debug)]
@@ -40,9 +43,21 @@ pub enum StorageError {
     /// Wrapper for `reqwest_middleware::Error`.
     #[error("Middleware error: {0}")]
     MiddlewareError(#[from] reqwest_middleware::Error),
-    /// Errors returned by the Cloud Storage API.
+    /// Errors returned by the Cloud Storage API that have no dedicated variant.
     #[error("API error: {0}")]
-    ApiError(String),
+    ApiError(FirebaseError),
+    /// The bucket or object does not exist (`NOT_FOUND`).
+    #[error("Not found: {0}")]
+    NotFound(FirebaseError),
+    /// A precondition such as an object generation match failed (`FAILED_PRECONDITION`).
+    #[error("Precondition failed: {0}")]
+    PreconditionFailed(FirebaseError),
+    /// A URL could not be built from the given arguments.
+    #[error("Invalid URL: {0}")]
+    InvalidUrl(#[from] url::ParseError),
+    /// A signed URL could not be generated (e.g., the private key is invalid).
+    #[error("Signing error: {0}")]
+    SigningError(#[from] SignerError),
     /// Wrapper for `serde_json::Error`.
     #[error("Serialization error: {0}")]
     SerializationError(#[from] serde_json::Error),
@@ -51,6 +66,28 @@ pub enum StorageError {
     ProjectIdMissing,
 }
 
+impl StorageError {
+    /// Returns the structured API error behind this error, if it was returned by the server.
+    pub fn firebase_error(&self) -> Option<&FirebaseError> {
+        match self {
+            StorageError::ApiError(e)
+            | StorageError::NotFound(e)
+            | StorageError::PreconditionFailed(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<FirebaseError> for StorageError {
+    fn from(error: FirebaseError) -> Self {
+        match error.status.as_str() {
+            "NOT_FOUND" => StorageError::NotFound(error),
+            "FAILED_PRECONDITION" => StorageError::PreconditionFailed(error),
+            _ => StorageError::ApiError(error),
+        }
+    }
+}
+
 /// Client for interacting with Cloud Storage for Firebase.
 #[derive(Clone)]
 pub struct FirebaseStorage {
@@ -64,16 +101,17 @@ impl FirebaseStorage {
     /// Creates a new `FirebaseStorage` instance.
     ///
     /// This is typically called via `FirebaseApp::storage()`.
+    /// If a Cloud Storage emulator host is configured, the client targets the emulator instead.
     pub fn new(middleware: AuthMiddleware) -> Self {
-        let retry_policy = ExponentialBackoff::builder().build_with_max_retries(3);
+        let (origin, middleware) = match &middleware.emulators().storage_host {
+            Some(host) => (emulator_origin(host), middleware.for_emulator()),
+            None => (STORAGE_ORIGIN.to_string(), middleware),
+        };
 
-        let client = ClientBuilder::new(Client::new())
-            .with(RetryTransientMiddleware::new_with_policy(retry_policy))
-            .with(middleware.clone())
-            .build();
+        let client = build_client(&middleware, "storage");
 
-        let project_id = middleware.key.project_id.clone().unwrap_or_default();
-        let base_url = STORAGE_V1_API.to_string();
+        let project_id = middleware.project_id().to_string();
+        let base_url = STORAGE_V1_API.replace("{origin}", &origin);
 
         Self {
             client,
@@ -83,9 +121,12 @@ impl FirebaseStorage {
         }
     }
 
-    #[cfg(test)]
-    pub(crate) fn new_with_client(client: ClientWithMiddleware, base_url: String, middleware: AuthMiddleware) -> Self {
-        let project_id = middleware.key.project_id.clone().unwrap_or_default();
+    /// Creates a new `FirebaseStorage` instance with a custom client and base URL.
+    ///
+    /// The client is used as is, so it must add authentication itself. `middleware` is only
+    /// used for signing URLs and for the project ID.
+    pub fn new_with_client(client: ClientWithMiddleware, base_url: String, middleware: AuthMiddleware) -> Self {
+        let project_id = middleware.project_id().to_string();
         Self {
             client,
             base_url,
@@ -99,12 +140,15 @@ impl FirebaseStorage {
     /// # Arguments
     ///
     /// * `name` - The name of the bucket (e.g. "my-project.appspot.com").
-    ///            If not provided, it attempts to use the default bucket name derived from the project ID
-    ///            (e.g., "{project_id}.appspot.com").
+    ///   If not provided, the app's `storage_bucket` option is used, falling back to the
+    ///   default bucket name derived from the project ID (e.g., "{project_id}.appspot.com").
     pub fn bucket(&self, name: Option<&str>) -> Bucket {
         let bucket_name = match name {
             Some(n) => n.to_string(),
-            None => format!("{}.appspot.com", self.project_id),
+            None => match &self.middleware.options().storage_bucket {
+                Some(bucket) => bucket.clone(),
+                None => format!("{}.appspot.com", self.project_id),
+            },
         };
 
         Bucket::new(self.client.clone(), self.base_url.clone(), bucket_name, self.middleware.clone())
@@ -104,8 +104,8 @@ async fn test_set_metadata() {
     mock.assert();
 }
 
-#[test]
-fn test_get_signed_url() {
+#[tokio::test]
+async fn test_get_signed_url() {
     let middleware = create_dummy_middleware();
     let storage = FirebaseStorage::new(middleware);
     let bucket = storage.bucket(Some("test-bucket"));
@@ -117,7 +117,7 @@ fn test_get_signed_url() {
         content_type: None,
     };
 
-    let url = file.get_signed_url(options).unwrap();
+    let url = file.get_signed_url(options).await.unwrap();
     
     // Basic validation
     assert!(url.starts_with("https://storage.googleapis.com/test-bucket/test-file.txt"));
@@ -130,6 +130,79 @@ fn test_get_signed_url() {
     assert!(url.contains("test%40test-project.iam.gserviceaccount.com"));
 }
 
+#[tokio::test]
+async fn test_get_signed_url_with_iam_signer() {
+    use crate::core::credential::StaticTokenCredential;
+    use crate::core::signer::IamSigner;
+    use std::sync::Arc;
+
+    let server = MockServer::start();
+    let credential = StaticTokenCredential::new("static-token").with_project_id("test-project");
+    let signer = IamSigner::new(credential.clone())
+        .with_service_account_id("signer@test-project.iam.gserviceaccount.com")
+        .with_base_url(server.url("/v1"));
+    let middleware = AuthMiddleware::from_credential(Arc::new(credential)).with_signer(Arc::new(signer));
+    let storage = FirebaseStorage::new(middleware);
+    let file = storage.bucket(Some("test-bucket")).file("test-file.txt");
+
+    let mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/v1/projects/-/serviceAccounts/signer@test-project.iam.gserviceaccount.com:signBlob")
+            .header("authorization", "Bearer static-token")
+            .body_includes("\"payload\"");
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({ "keyId": "key-1", "signedBlob": "AQID" }));
+    });
+
+    let options = GetSignedUrlOptions {
+        method: SignedUrlMethod::GET,
+        expires: SystemTime::now() + Duration::from_secs(3600),
+        content_type: None,
+    };
+    let url = file.get_signed_url(options).await.unwrap();
+
+    assert!(url.contains("signer%40test-project.iam.gserviceaccount.com"));
+    assert!(url.ends_with("X-Goog-Signature=010203"));
+    mock.assert();
+}
+
+#[tokio::test]
+async fn test_get_signed_url_keeps_signer_error() {
+    use crate::core::credential::StaticTokenCredential;
+    use crate::core::signer::{IamSigner, SignerError};
+    use std::sync::Arc;
+
+    let server = MockServer::start();
+    let credential = StaticTokenCredential::new("static-token").with_project_id("test-project");
+    let signer = IamSigner::new(credential.clone())
+        .with_service_account_id("signer@test-project.iam.gserviceaccount.com")
+        .with_base_url(server.url("/v1"));
+    let middleware = AuthMiddleware::from_credential(Arc::new(credential)).with_signer(Arc::new(signer));
+    let file = FirebaseStorage::new(middleware).bucket(Some("test-bucket")).file("test-file.txt");
+
+    let mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/v1/projects/-/serviceAccounts/signer@test-project.iam.gserviceaccount.com:signBlob");
+        then.status(403)
+            .header("content-type", "application/json")
+            .json_body(json!({ "error": { "code": 403, "message": "Permission denied", "status": "PERMISSION_DENIED" } }));
+    });
+
+    let options = GetSignedUrlOptions {
+        method: SignedUrlMethod::GET,
+        expires: SystemTime::now() + Duration::from_secs(3600),
+        content_type: None,
+    };
+    let result = file.get_signed_url(options).await;
+
+    assert!(matches!(
+        result,
+        Err(StorageError::SigningError(SignerError::ApiError(_)))
+    ));
+    mock.assert();
+}
+
 #[tokio::test]
 async fn test_save_file() {
     let server = MockServer::start();
@@ -234,3 +307,38 @@ async fn test_get_metadata() {
     
     mock.assert();
 }
+
+#[tokio::test]
+async fn test_emulator_routing() {
+    let server = MockServer::start();
+    let emulators = crate::core::emulator::EmulatorConfig::default()
+        .with_storage_host(server.url(""));
+    let middleware = create_dummy_middleware().with_emulators(emulators);
+    let storage = FirebaseStorage::new(middleware);
+    let file = storage.bucket(Some("test-bucket")).file("test-file.txt");
+
+    let upload_mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/upload/storage/v1/b/test-bucket/o")
+            .query_param("name", "test-file.txt")
+            .header("authorization", "Bearer owner");
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({ "name": "test-file.txt", "bucket": "test-bucket" }));
+    });
+
+    let download_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/storage/v1/b/test-bucket/o/test-file.txt")
+            .query_param("alt", "media")
+            .header("authorization", "Bearer owner");
+        then.status(200).body("Hello, World!");
+    });
+
+    file.save("Hello, World!", "text/plain").await.unwrap();
+    let bytes = file.download().await.unwrap();
+    assert_eq!(bytes, "Hello, World!".as_bytes());
+
+    upload_mock.assert();
+    download_mock.assert();
+}
@@ -0,0 +1,311 @@
+//! Cassette-style recording and replay of API calls for tests.
+//!
+//! A [`CassetteRecorder`] sends requests over the network and records every request/response
+//! pair; a [`CassetteReplayer`] answers requests from a recorded [`Cassette`] without any
+//! network access. Both are [`HttpTransport`]s, so they sit below the SDK's retry and
+//! authentication layers and work with every service client:
+//!
+//! ```rust,no_run
+//! use firebase_admin_sdk::core::credential::StaticTokenCredential;
+//! use firebase_admin_sdk::testing::{CassetteRecorder, CassetteReplayer};
+//! use firebase_admin_sdk::FirebaseApp;
+//! use std::sync::Arc;
+//!
+//! # async fn run() {
+//! // Record once against the real API (or an emulator)...
+//! let recorder = Arc::new(CassetteRecorder::new());
+//! let app = FirebaseApp::builder().transport(recorder.clone()).build().unwrap();
+//! app.auth().get_user("some-uid").await.unwrap();
+//! recorder.save("tests/fixtures/get_user.json").unwrap();
+//!
+//! // ...then replay deterministically in CI.
+//! let replayer = CassetteReplayer::from_file("tests/fixtures/get_user.json").unwrap();
+//! let app = FirebaseApp::builder()
+//!     .credential(StaticTokenCredential::new("unused").with_project_id("my-project"))
+//!     .transport(replayer)
+//!     .build()
+//!     .unwrap();
+//! app.auth().get_user("some-uid").await.unwrap();
+//! # }
+//! ```
+//!
+//! Bearer tokens are redacted before interactions are stored. Responses are read to the end
+//! before they are recorded, so streaming calls such as Firestore `listen()` cannot be
+//! recorded.
+
+use crate::core::transport::HttpTransport;
+use async_trait::async_trait;
+use reqwest::{Client, Request, Response};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Mutex;
+use thiserror::Error;
+
+/// The value stored in place of `Authorization` header credentials.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Errors that can occur while loading or saving a cassette.
+#[derive(Error, Debug)]
+pub enum CassetteError {
+    /// The cassette file could not be read or written.
+    #[error("Cassette I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    /// The cassette file is not valid JSON.
+    #[error("Invalid cassette: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// A recorded sequence of request/response pairs, stored as a JSON fixture.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Cassette {
+    /// The interactions in the order they were recorded.
+    pub interactions: Vec<Interaction>,
+}
+
+impl Cassette {
+    /// Loads a cassette from a JSON file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, CassetteError> {
+        let bytes = std::fs::read(path)?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
+    /// Saves the cassette as pretty-printed JSON, creating parent directories as needed.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), CassetteError> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_vec_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// A single request and the response it received.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Interaction {
+    /// The request sent.
+    pub request: RecordedRequest,
+    /// The response received.
+    pub response: RecordedResponse,
+}
+
+/// A recorded request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    /// The HTTP method (e.g., `GET`).
+    pub method: String,
+    /// The full request URL, including the query string.
+    pub url: String,
+    /// The request headers, with credentials redacted.
+    #[serde(default)]
+    pub headers: Vec<(String, String)>,
+    /// The request body, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<RecordedBody>,
+}
+
+/// A recorded response.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    /// The HTTP status code.
+    pub status: u16,
+    /// The response headers.
+    #[serde(default)]
+    pub headers: Vec<(String, String)>,
+    /// The response body.
+    #[serde(default)]
+    pub body: RecordedBody,
+}
+
+/// A request or response body. Text bodies are stored as is, binary bodies hex-encoded.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RecordedBody {
+    /// The body text, or its hex encoding if `binary` is set.
+    pub text: String,
+    /// Whether `text` holds hex-encoded binary data.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub binary: bool,
+}
+
+impl RecordedBody {
+    fn from_bytes(bytes: &[u8]) -> Self {
+        match std::str::from_utf8(bytes) {
+            Ok(text) => Self {
+                text: text.to_string(),
+                binary: false,
+            },
+            Err(_) => Self {
+                text: hex::encode(bytes),
+                binary: true,
+            },
+        }
+    }
+
+    /// Returns the raw body bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        if self.binary {
+            hex::decode(&self.text).unwrap_or_default()
+        } else {
+            self.text.clone().into_bytes()
+        }
+    }
+}
+
+/// Copies headers, replacing the credentials of `Authorization` headers with [`REDACTED`].
+fn record_headers(headers: &http::HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = String::from_utf8_lossy(value.as_bytes()).into_owned();
+            let value = if name == http::header::AUTHORIZATION {
+                match value.split_once(' ') {
+                    Some((scheme, _)) => format!("{} {}", scheme, REDACTED),
+                    None => REDACTED.to_string(),
+                }
+            } else {
+                value
+            };
+            (name.to_string(), value)
+        })
+        .collect()
+}
+
+fn to_response(recorded: &RecordedResponse) -> Response {
+    let mut builder = http::Response::builder().status(recorded.status);
+    for (name, value) in &recorded.headers {
+        builder = builder.header(name, value);
+    }
+    let response = builder
+        .body(recorded.body.to_bytes())
+        .unwrap_or_else(|_| http::Response::new(Vec::new()));
+    Response::from(response)
+}
+
+/// An [`HttpTransport`] that sends requests with `reqwest` and records every interaction.
+#[derive(Debug, Default)]
+pub struct CassetteRecorder {
+    client: Client,
+    cassette: Mutex<Cassette>,
+}
+
+impl CassetteRecorder {
+    /// Creates a recorder that sends requests with a default `reqwest::Client`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a recorder that sends requests with the given client.
+    pub fn with_client(client: Client) -> Self {
+        Self {
+            client,
+            cassette: Mutex::default(),
+        }
+    }
+
+    /// Returns the interactions recorded so far.
+    pub fn cassette(&self) -> Cassette {
+        self.cassette.lock().unwrap().clone()
+    }
+
+    /// Saves the interactions recorded so far to a JSON file.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), CassetteError> {
+        self.cassette().save(path)
+    }
+}
+
+#[async_trait]
+impl HttpTransport for CassetteRecorder {
+    async fn execute(&self, request: Request) -> reqwest_middleware::Result<Response> {
+        let recorded_request = RecordedRequest {
+            method: request.method().to_string(),
+            url: request.url().to_string(),
+            headers: record_headers(request.headers()),
+            body: request
+                .body()
+                .and_then(|b| b.as_bytes())
+                .map(RecordedBody::from_bytes),
+        };
+
+        let response = self.client.execute(request).await?;
+        let status = response.status().as_u16();
+        let headers = response.headers().clone();
+        let body = response.bytes().await?;
+
+        let recorded_response = RecordedResponse {
+            status,
+            headers: record_headers(&headers),
+            body: RecordedBody::from_bytes(&body),
+        };
+        let replayed = to_response(&recorded_response);
+
+        self.cassette.lock().unwrap().interactions.push(Interaction {
+            request: recorded_request,
+            response: recorded_response,
+        });
+
+        Ok(replayed)
+    }
+}
+
+/// An [`HttpTransport`] that answers requests from a [`Cassette`] without network access.
+///
+/// Each request is answered by the first unused interaction with the same method and URL, so
+/// repeated calls to one endpoint replay in recording order. Requests without a matching
+/// interaction fail with a middleware error.
+#[derive(Debug)]
+pub struct CassetteReplayer {
+    interactions: Mutex<Vec<Option<Interaction>>>,
+}
+
+impl CassetteReplayer {
+    /// Creates a replayer for the given cassette.
+    pub fn new(cassette: Cassette) -> Self {
+        Self {
+            interactions: Mutex::new(cassette.interactions.into_iter().map(Some).collect()),
+        }
+    }
+
+    /// Loads the cassette to replay from a JSON file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, CassetteError> {
+        Ok(Self::new(Cassette::load(path)?))
+    }
+
+    /// Returns the number of recorded interactions that have not been replayed yet.
+    pub fn remaining(&self) -> usize {
+        self.interactions
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|i| i.is_some())
+            .count()
+    }
+}
+
+#[async_trait]
+impl HttpTransport for CassetteReplayer {
+    async fn execute(&self, request: Request) -> reqwest_middleware::Result<Response> {
+        let method = request.method().as_str();
+        let url = request.url().as_str();
+
+        let mut interactions = self.interactions.lock().unwrap();
+        let interaction = interactions
+            .iter_mut()
+            .find(|slot| {
+                slot.as_ref()
+                    .is_some_and(|i| i.request.method == method && i.request.url == url)
+            })
+            .and_then(Option::take)
+            .ok_or_else(|| {
+                reqwest_middleware::Error::Middleware(anyhow::anyhow!(
+                    "No recorded interaction for {} {}",
+                    method,
+                    url
+                ))
+            })?;
+
+        Ok(to_response(&interaction.response))
+    }
+}
+
+#[cfg(all(test, feature = "auth", feature = "firestore", feature = "messaging"))]
+mod tests;
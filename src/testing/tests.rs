@@ -0,0 +1,116 @@
+use super::{Cassette, CassetteRecorder, CassetteReplayer, REDACTED};
+use crate::core::credential::StaticTokenCredential;
+use crate::core::emulator::EmulatorConfig;
+use crate::messaging::models::Message;
+use crate::messaging::FirebaseMessaging;
+use crate::FirebaseApp;
+use httpmock::prelude::*;
+use serde_json::json;
+use std::sync::Arc;
+
+fn app_with_transport(transport: impl crate::core::transport::HttpTransport + 'static, host: &str) -> FirebaseApp {
+    FirebaseApp::builder()
+        .credential(StaticTokenCredential::new("secret-token").with_project_id("test-project"))
+        .transport(transport)
+        .emulators(
+            EmulatorConfig::default()
+                .with_auth_host(host)
+                .with_firestore_host(host),
+        )
+        .build()
+        .unwrap()
+}
+
+/// Runs an Auth, Firestore and Messaging flow, returning the observed results.
+async fn run_flow(app: &FirebaseApp, send_url: String) -> (String, serde_json::Value, String) {
+    let user = app.auth().get_user("test-uid").await.unwrap();
+
+    let firestore = app.firestore();
+    let snapshot = firestore
+        .collection("users")
+        .doc("alice")
+        .get()
+        .await
+        .unwrap();
+    let data: serde_json::Value = snapshot.data().unwrap().unwrap();
+
+    let messaging = FirebaseMessaging::new_with_url(
+        app.middleware.clone(),
+        send_url,
+        "http://unused/batch".to_string(),
+        "http://unused".to_string(),
+    );
+    let message = Message {
+        token: Some("device-token".to_string()),
+        ..Default::default()
+    };
+    let name = messaging.send(&message, false).await.unwrap();
+
+    (user.local_id, data, name)
+}
+
+#[tokio::test]
+async fn test_record_and_replay() {
+    let server = MockServer::start();
+    let host = server.address().to_string();
+    let send_url = server.url("/v1/projects/test-project/messages:send");
+
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/identitytoolkit.googleapis.com/v1/projects/test-project/accounts:lookup");
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "users": [{ "localId": "test-uid", "emailVerified": false, "disabled": false }]
+            }));
+    });
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/v1/projects/test-project/databases/(default)/documents/users/alice");
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({
+                "name": "projects/test-project/databases/(default)/documents/users/alice",
+                "fields": { "age": { "integerValue": "30" } },
+                "createTime": "2023-01-01T00:00:00Z",
+                "updateTime": "2023-01-01T00:00:00Z"
+            }));
+    });
+    let send_mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/v1/projects/test-project/messages:send")
+            .header("authorization", "Bearer secret-token");
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(json!({ "name": "projects/test-project/messages/1" }));
+    });
+
+    let recorder = Arc::new(CassetteRecorder::new());
+    let recorded = run_flow(&app_with_transport(recorder.clone(), &host), send_url.clone()).await;
+    send_mock.assert();
+
+    let path = std::env::temp_dir().join(format!("firebase-cassette-{}.json", std::process::id()));
+    recorder.save(&path).unwrap();
+    drop(server);
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    assert!(!contents.contains("secret-token"));
+    let cassette = Cassette::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(cassette.interactions.len(), 3);
+    let send = &cassette.interactions[2].request;
+    assert!(send
+        .headers
+        .contains(&("authorization".to_string(), format!("Bearer {}", REDACTED))));
+    assert!(send.body.as_ref().unwrap().text.contains("device-token"));
+
+    let replayer = Arc::new(CassetteReplayer::new(cassette));
+    let app = app_with_transport(replayer.clone(), &host);
+    let replayed = run_flow(&app, send_url).await;
+    assert_eq!(replayed, recorded);
+    assert_eq!(replayed.2, "projects/test-project/messages/1");
+    assert_eq!(replayer.remaining(), 0);
+
+    // Every interaction is replayed once.
+    assert!(app.auth().get_user("test-uid").await.is_err());
+}